@@ -0,0 +1,105 @@
+//! Runs the benchmark suite of `chess::bench`.
+//!
+//! `cargo bench --bench suite -- [--save FILE] [--baseline FILE] [NAME...]` runs the problems
+//! named (all of them by default), compares them with the measurements of `--baseline` and
+//! saves the new measurements to `--save`. A NAME such as `queens8` runs a problem with
+//! every solver, and one such as `queens8/dlx` with that solver only. The times of
+//! the two backends of `chess count` are then compared for the problems run with both.
+
+use chess::bench::{self, Measurement, SOLVERS, SUITE};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs::File;
+use std::io::BufReader;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The system allocator, keeping track of the bytes allocated and their peak.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            grow(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() {
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+            grow(new_size);
+        }
+        new_pointer
+    }
+}
+
+fn grow(size: usize) {
+    let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(allocated, Ordering::Relaxed);
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn main() {
+    if let Err(message) = run() {
+        eprintln!("bench: {}", message);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut save = None;
+    let mut baseline = Vec::new();
+    let mut names = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--save" => save = Some(args.next().ok_or("--save needs a file")?),
+            "--baseline" => {
+                let path = args.next().ok_or("--baseline needs a file")?;
+                baseline = File::open(&path)
+                    .and_then(|file| bench::read_baseline(BufReader::new(file)))
+                    .map_err(|e| format!("cannot read {}: {}", path, e))?;
+            }
+            // Passed by `cargo bench`.
+            "--bench" => {}
+            name => names.push(name.to_string()),
+        }
+    }
+    let mut measurements: Vec<Measurement> = Vec::new();
+    for problem in SUITE {
+        for solver in SOLVERS {
+            let name = problem.measurement_name(solver);
+            if !names.is_empty() && !names.iter().any(|n| *n == problem.name || *n == name) {
+                continue;
+            }
+            let before = ALLOCATED.load(Ordering::Relaxed);
+            PEAK.store(before, Ordering::Relaxed);
+            let mut measurement = problem.measure_with(solver);
+            measurement.memory = PEAK.load(Ordering::Relaxed) - before;
+            measurements.push(measurement);
+        }
+    }
+    println!("{}", bench::report(&measurements, &baseline));
+    let comparison = bench::compare_backends(&measurements);
+    if comparison.lines().count() > 1 {
+        println!("\n{}", comparison);
+    }
+    if let Some(path) = save {
+        File::create(&path)
+            .and_then(|file| bench::write_baseline(file, &measurements))
+            .map_err(|e| format!("cannot write {}: {}", path, e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//! Peaceable coexisting armies.
+//!
+//! A white and a black army of the same size share a board so that no piece attacks a
+//! piece of the other colour; pieces of the same colour may attack each other freely.
+//! For queens, the largest possible army size on an n×n board is OEIS A250000.
+//!
+//! The search places the white queens and counts the squares they leave unattacked,
+//! where the black queens can go. Those free squares only shrink as more white queens
+//! are added, so a branch is abandoned as soon as fewer free squares remain than the
+//! army size sought.
+
+use crate::{Board, ChessPiece, Colour, Piece};
+
+/// The largest board side [`peaceable_queens`] accepts; larger boards take too long.
+pub const MAX_SIZE: i8 = 10;
+
+/// Returns the largest `m` such that `m` white and `m` black queens fit peaceably on an
+/// `n`×`n` board, together with one such board.
+///
+/// The queens of the board have colours, and each of them is safe on the board of the
+/// others according to [`Board::is_safe`].
+///
+/// # Panics
+///
+/// Panics if `n` is not between 1 and [`MAX_SIZE`].
+///
+/// # Example
+///
+/// ```
+/// use chess::armies;
+///
+/// let (size, board) = armies::peaceable_queens(5);
+/// assert_eq!(size, 4);
+/// assert_eq!(board.pieces.len(), 8);
+/// ```
+pub fn peaceable_queens(n: i8) -> (usize, Board) {
+    assert!(
+        (1..=MAX_SIZE).contains(&n),
+        "peaceable armies are searched on boards of 1 to {} squares a side",
+        MAX_SIZE
+    );
+    let side = n as usize;
+    let squares = side * side;
+    let full = (1u128 << squares) - 1;
+    // The squares attacked by a queen on each square, its own included.
+    let cover: Vec<u128> = (0..squares)
+        .map(|a| {
+            let queen = queen_at(a, side, None);
+            (0..squares)
+                .filter(|&b| queen.attacks(queen_at(b, side, None)))
+                .fold(0, |mask, b| mask | 1 << b)
+        })
+        .collect();
+
+    let mut best = (0, Vec::new());
+    let mut whites = Vec::new();
+    for size in 1..=squares / 2 {
+        if !place_whites(0, 0, size, &cover, full, &mut whites) {
+            break;
+        }
+        best = (size, whites.clone());
+        whites.clear();
+    }
+
+    let (size, whites) = best;
+    let covered = whites.iter().fold(0, |mask, &a| mask | cover[a]);
+    let blacks = (0..squares)
+        .filter(|&a| full & !covered & 1 << a != 0)
+        .take(size);
+    let mut pieces: Vec<Piece> = whites
+        .iter()
+        .map(|&a| queen_at(a, side, Some(Colour::White)))
+        .chain(blacks.map(|a| queen_at(a, side, Some(Colour::Black))))
+        .collect();
+    pieces.sort();
+    (size, Board::new(n, n, pieces))
+}
+
+/// Places `size` white queens on squares from `start` on, so that at least `size`
+/// squares remain unattacked. The squares chosen are left in `whites` on success.
+fn place_whites(
+    start: usize,
+    covered: u128,
+    size: usize,
+    cover: &[u128],
+    full: u128,
+    whites: &mut Vec<usize>,
+) -> bool {
+    let free = (full & !covered).count_ones() as usize;
+    if free < size {
+        return false;
+    }
+    if whites.len() == size {
+        return true;
+    }
+    let missing = size - whites.len();
+    for a in start..=cover.len() - missing {
+        whites.push(a);
+        if place_whites(a + 1, covered | cover[a], size, cover, full, whites) {
+            return true;
+        }
+        whites.pop();
+    }
+    false
+}
+
+fn queen_at(square: usize, side: usize, colour: Option<Colour>) -> Piece {
+    Piece {
+        row: (square / side) as i8 + 1,
+        col: (square % side) as i8 + 1,
+        piece: ChessPiece::Queen,
+        colour,
+    }
+}
@@ -0,0 +1,83 @@
+//! Choice of the algorithm used to solve a problem.
+
+use crate::bigint::BigUint;
+use crate::notation::ParseError;
+use crate::{dlx, search, solution, Board, ChessPiece};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// The algorithms that can solve a problem. They all find the same solutions.
+///
+/// * `Backtracking` - [`solution`] to enumerate, and the search of [`crate::search`] to
+///   count
+/// * `DancingLinks` - The exact cover formulation of [`crate::dlx`]
+///
+/// # Example
+///
+/// ```
+/// use chess::backend::Backend;
+/// use chess::{Board, ChessPiece};
+///
+/// let board = Board::new(3, 3, Vec::new());
+/// let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+/// let backend: Backend = "dlx".parse().unwrap();
+/// assert_eq!(backend.solve(&board, &pieces), Backend::Backtracking.solve(&board, &pieces));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum Backend {
+    #[default]
+    Backtracking,
+    DancingLinks,
+}
+
+impl Backend {
+    /// Finds all the configurations obtained by placing `pieces` on `board`.
+    pub fn solve(self, board: &Board, pieces: &[ChessPiece]) -> HashSet<Rc<Board>> {
+        let mut solutions: HashSet<Rc<Board>> = HashSet::new();
+        match self {
+            Backend::Backtracking if pieces.is_empty() => {
+                solutions.insert(Rc::new(board.clone()));
+            }
+            Backend::Backtracking => {
+                let mut board_stack: VecDeque<(Rc<Board>, &[ChessPiece])> = VecDeque::new();
+                board_stack.push_front((Rc::new(board.clone()), pieces));
+                solution(&mut board_stack, &mut solutions, &mut HashSet::new());
+            }
+            Backend::DancingLinks => dlx::for_each_solution(board, pieces, |solution| {
+                solutions.insert(Rc::new(solution.clone()));
+            }),
+        }
+        solutions
+    }
+
+    /// Counts the configurations obtained by placing `pieces` on `board`.
+    pub fn count(self, board: &Board, pieces: &[ChessPiece]) -> BigUint {
+        match self {
+            Backend::Backtracking => search::count(board, pieces),
+            Backend::DancingLinks => dlx::count(board, pieces),
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Backend::Backtracking => "backtracking",
+            Backend::DancingLinks => "dlx",
+        })
+    }
+}
+
+impl FromStr for Backend {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Backend, ParseError> {
+        match s {
+            "backtracking" => Ok(Backend::Backtracking),
+            "dlx" => Ok(Backend::DancingLinks),
+            _ => Err(ParseError::new(format!("unknown backend `{}`", s))),
+        }
+    }
+}
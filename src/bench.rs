@@ -0,0 +1,314 @@
+//! A fixed suite of problems to track the performance of the solver.
+//!
+//! Each problem of [`SUITE`] is solved by every [`Solver`], recording its solution
+//! count, the number of boards the solver went through and the time it took.
+//! Measurements are saved as a baseline file, one problem and solver per line, and later
+//! runs are compared against it. `cargo bench` runs the suite, also recording the peak
+//! memory allocated by each problem.
+//!
+//! A baseline file looks like this, with times in seconds and memory in bytes:
+//!
+//! ```text
+//! # chess bench
+//! challenge 3063828 4815155 5.124 2816
+//! challenge/solution 3063828 2151031 14.203 1620893184
+//! challenge/dlx 3063828 0 5.541 31744
+//! ```
+
+use crate::notation::{parse_pieces, ParseError};
+use crate::{dlx, search, solution_with_stats, Board, ChessPiece};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const HEADER: &str = "# chess bench";
+
+/// A problem of the suite.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Problem {
+    pub name: &'static str,
+    pub rows: i8,
+    pub cols: i8,
+    /// The pieces, in notation.
+    pub pieces: &'static str,
+}
+
+/// The problems of the suite: the original challenge, queens, knights and a larger mixed
+/// problem.
+pub const SUITE: &[Problem] = &[
+    Problem {
+        name: "challenge",
+        rows: 7,
+        cols: 7,
+        pieces: "2K2Q2B1N",
+    },
+    Problem {
+        name: "queens8",
+        rows: 8,
+        cols: 8,
+        pieces: "8Q",
+    },
+    Problem {
+        name: "queens10",
+        rows: 10,
+        cols: 10,
+        pieces: "10Q",
+    },
+    Problem {
+        name: "knights6",
+        rows: 6,
+        cols: 6,
+        pieces: "6N",
+    },
+    Problem {
+        name: "mixed6x9",
+        rows: 6,
+        cols: 9,
+        pieces: "2K1Q1R1B1N",
+    },
+];
+
+/// The ways the suite solves each problem.
+///
+/// * `Search` - The counting search of [`crate::search`]
+/// * `Solution` - [`solution`](crate::solution), which `chess` and `chess solve` run
+/// * `DancingLinks` - The exact cover search of [`crate::dlx`], `--backend dlx`
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Solver {
+    Search,
+    Solution,
+    DancingLinks,
+}
+
+/// Every solver, in the order the suite runs them.
+pub const SOLVERS: [Solver; 3] = [Solver::Search, Solver::Solution, Solver::DancingLinks];
+
+/// What a run of a problem did.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Measurement {
+    /// See [`Problem::measurement_name`].
+    pub name: String,
+    pub solutions: u64,
+    /// The boards the solver went through: those counted by [`search::nodes`] for the
+    /// search, the boards expanded for [`solution`](crate::solution), and 0 for Dancing
+    /// Links, which doesn't go through boards.
+    pub nodes: u64,
+    pub time: Duration,
+    /// The peak number of bytes allocated during the run, or 0 when not tracked.
+    pub memory: usize,
+}
+
+impl Problem {
+    /// Solves the problem once with the search, leaving `memory` at 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::bench::SUITE;
+    ///
+    /// let queens = SUITE.iter().find(|problem| problem.name == "queens8").unwrap();
+    /// assert_eq!(queens.measure().solutions, 92);
+    /// ```
+    pub fn measure(&self) -> Measurement {
+        self.measure_with(Solver::Search)
+    }
+
+    /// Returns the name of the measurements of the problem with `solver`: the name of
+    /// the problem, followed by `/solution` or `/dlx` for those solvers.
+    pub fn measurement_name(&self, solver: Solver) -> String {
+        match solver {
+            Solver::Search => self.name.to_string(),
+            Solver::Solution => format!("{}/solution", self.name),
+            Solver::DancingLinks => format!("{}/dlx", self.name),
+        }
+    }
+
+    /// Solves the problem once with `solver`, leaving `memory` at 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::bench::{Solver, SUITE};
+    ///
+    /// let queens = SUITE.iter().find(|problem| problem.name == "queens8").unwrap();
+    /// let measurement = queens.measure_with(Solver::DancingLinks);
+    /// assert_eq!(measurement.name, "queens8/dlx");
+    /// assert_eq!(measurement.solutions, 92);
+    /// ```
+    pub fn measure_with(&self, solver: Solver) -> Measurement {
+        let board = Board::new(self.rows, self.cols, Vec::new());
+        let pieces = parse_pieces(self.pieces).expect("the suite is written in notation");
+        let start = Instant::now();
+        let (solutions, nodes) = match solver {
+            Solver::Search => {
+                let mut solutions = 0;
+                let mut nodes = 0;
+                search::for_each_node(&board, &pieces, |_, pieces| {
+                    nodes += 1;
+                    solutions += pieces.is_empty() as u64;
+                });
+                (solutions, nodes)
+            }
+            Solver::Solution => {
+                let mut board_stack: VecDeque<(Rc<Board>, &[ChessPiece])> = VecDeque::new();
+                board_stack.push_front((Rc::new(board), &pieces));
+                let mut solutions = HashSet::new();
+                let (solutions, stats) =
+                    solution_with_stats(&mut board_stack, &mut solutions, &mut HashSet::new());
+                (solutions.len() as u64, stats.expanded)
+            }
+            Solver::DancingLinks => {
+                let solutions = dlx::count(&board, &pieces)
+                    .to_u64()
+                    .expect("the suite's counts fit in u64");
+                (solutions, 0)
+            }
+        };
+        Measurement {
+            name: self.measurement_name(solver),
+            solutions,
+            nodes,
+            time: start.elapsed(),
+            memory: 0,
+        }
+    }
+}
+
+/// Writes measurements as a baseline file.
+pub fn write_baseline<W: Write>(mut writer: W, measurements: &[Measurement]) -> io::Result<()> {
+    writeln!(writer, "{}", HEADER)?;
+    for measurement in measurements {
+        writeln!(
+            writer,
+            "{} {} {} {:.3} {}",
+            measurement.name,
+            measurement.solutions,
+            measurement.nodes,
+            measurement.time.as_secs_f64(),
+            measurement.memory
+        )?;
+    }
+    Ok(())
+}
+
+/// Reads a baseline file written by [`write_baseline`].
+pub fn read_baseline<R: BufRead>(reader: R) -> io::Result<Vec<Measurement>> {
+    let mut lines = reader.lines();
+    if lines.next().transpose()?.as_deref() != Some(HEADER) {
+        return Err(ParseError::new("not a benchmark baseline").into());
+    }
+    let mut measurements = Vec::new();
+    for line in lines {
+        let line = line?;
+        let invalid = || ParseError::new(format!("invalid measurement `{}`", line));
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [name, solutions, nodes, seconds, memory] = fields[..] else {
+            return Err(invalid().into());
+        };
+        let seconds: f64 = seconds.parse().map_err(|_| invalid())?;
+        measurements.push(Measurement {
+            name: name.to_string(),
+            solutions: solutions.parse().map_err(|_| invalid())?,
+            nodes: nodes.parse().map_err(|_| invalid())?,
+            time: Duration::try_from_secs_f64(seconds).map_err(|_| invalid())?,
+            memory: memory.parse().map_err(|_| invalid())?,
+        });
+    }
+    Ok(measurements)
+}
+
+/// Formats measurements as a table, with the change from the baseline measurement of the
+/// same name when there is one. A changed solution count is flagged as an error, since
+/// it means the solver is broken rather than slower.
+pub fn report(measurements: &[Measurement], baseline: &[Measurement]) -> String {
+    let mut report = format!(
+        "{:<20} {:>12} {:>12} {:>10} {:>10}",
+        "problem", "solutions", "nodes", "time", "memory"
+    );
+    for measurement in measurements {
+        let _ = write!(
+            report,
+            "\n{:<20} {:>12} {:>12} {:>9.3}s {:>8}KiB",
+            measurement.name,
+            measurement.solutions,
+            measurement.nodes,
+            measurement.time.as_secs_f64(),
+            measurement.memory / 1024
+        );
+        let Some(before) = baseline
+            .iter()
+            .find(|before| before.name == measurement.name)
+        else {
+            continue;
+        };
+        if before.solutions != measurement.solutions {
+            let _ = write!(
+                report,
+                "  ERROR: {} solutions in the baseline",
+                before.solutions
+            );
+            continue;
+        }
+        let _ = write!(
+            report,
+            "  nodes {}, time {}, memory {}",
+            change(before.nodes as f64, measurement.nodes as f64),
+            change(before.time.as_secs_f64(), measurement.time.as_secs_f64()),
+            change(before.memory as f64, measurement.memory as f64)
+        );
+    }
+    report
+}
+
+/// Formats the time of the two backends of `chess count`, the search for
+/// `backtracking` and Dancing Links for `dlx`, side by side for every problem of the
+/// suite measured with both, with the time of Dancing Links relative to the search.
+///
+/// # Example
+///
+/// ```
+/// use chess::bench::{self, Solver, SUITE};
+///
+/// let queens = SUITE.iter().find(|problem| problem.name == "queens8").unwrap();
+/// let measurements = [queens.measure(), queens.measure_with(Solver::DancingLinks)];
+/// let comparison = bench::compare_backends(&measurements);
+/// assert_eq!(comparison.lines().count(), 2);
+/// assert!(comparison.lines().nth(1).unwrap().starts_with("queens8"));
+/// ```
+pub fn compare_backends(measurements: &[Measurement]) -> String {
+    let mut comparison = format!(
+        "{:<10} {:>13} {:>10} {:>10}",
+        "problem", "backtracking", "dlx", "dlx time"
+    );
+    let find = |name: String| measurements.iter().find(|m| m.name == name);
+    for problem in SUITE {
+        let (Some(search), Some(dlx)) = (
+            find(problem.measurement_name(Solver::Search)),
+            find(problem.measurement_name(Solver::DancingLinks)),
+        ) else {
+            continue;
+        };
+        let _ = write!(
+            comparison,
+            "\n{:<10} {:>12.3}s {:>9.3}s {:>9.2}x",
+            problem.name,
+            search.time.as_secs_f64(),
+            dlx.time.as_secs_f64(),
+            dlx.time.as_secs_f64() / search.time.as_secs_f64()
+        );
+    }
+    comparison
+}
+
+/// Formats the relative change from `before` to `after`.
+fn change(before: f64, after: f64) -> String {
+    if before == after {
+        "=".to_string()
+    } else if before == 0.0 {
+        "new".to_string()
+    } else {
+        format!("{:+.1}%", (after - before) / before * 100.0)
+    }
+}
@@ -0,0 +1,260 @@
+//! Arbitrary-precision unsigned integers for solution counts.
+//!
+//! Counts grow exponentially with the size of the board and quickly exceed `u64`. This
+//! is a small base 2^32 implementation covering what counting needs, so the crate keeps
+//! no external dependency.
+
+use crate::rng::Rng;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul};
+use std::str::FromStr;
+
+/// An unsigned integer of any size.
+///
+/// # Example
+///
+/// ```
+/// use chess::bigint::BigUint;
+///
+/// let mut count = BigUint::from(u64::MAX);
+/// count += &BigUint::from(1);
+/// assert_eq!(count.to_string(), "18446744073709551616");
+/// assert_eq!(&count * &count, "340282366920938463463374607431768211456".parse().unwrap());
+/// assert!(count > BigUint::from(u64::MAX));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct BigUint {
+    /// Base 2^32 digits, least significant first, without trailing zeros.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// Returns zero.
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    /// Returns `true` if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Returns the number as a `u64`, or `None` if it doesn't fit.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [low] => Some(*low as u64),
+            [low, high] => Some((*high as u64) << 32 | *low as u64),
+            _ => None,
+        }
+    }
+
+    /// Returns a number drawn uniformly from `0..self` with `rng`.
+    ///
+    /// Numbers that fit in a `u64` are drawn with [`Rng::below`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is zero.
+    pub fn random_below(&self, rng: &mut Rng) -> BigUint {
+        if let Some(bound) = self.to_u64() {
+            return BigUint::from(rng.below(bound));
+        }
+        // Draw as many bits as the bound has until the number falls below it, which
+        // takes fewer than two draws on average.
+        let top = *self.limbs.last().expect("the number is not zero");
+        loop {
+            let mut limbs: Vec<u32> = self.limbs.iter().map(|_| rng.next_u64() as u32).collect();
+            *limbs.last_mut().expect("the number is not zero") &= u32::MAX >> top.leading_zeros();
+            let mut number = BigUint { limbs };
+            number.trim();
+            if number < *self {
+                return number;
+            }
+        }
+    }
+
+    /// Multiplies the number by a small factor in place and adds `addend`.
+    fn multiply_add_small(&mut self, factor: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in &mut self.limbs {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+        self.trim();
+    }
+
+    /// Divides the number by a small divisor in place and returns the remainder.
+    fn divide_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let current = remainder << 32 | *limb as u64;
+            *limb = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        self.trim();
+        remainder as u32
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> BigUint {
+        let mut number = BigUint {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        };
+        number.trim();
+        number
+    }
+}
+
+impl AddAssign<u64> for BigUint {
+    fn add_assign(&mut self, other: u64) {
+        *self += &BigUint::from(other);
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = 0u64;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let sum = *limb as u64 + other.limbs.get(i).copied().unwrap_or(0) as u64 + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+            if carry == 0 && i >= other.limbs.len() {
+                break;
+            }
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut sum = self.clone();
+        sum += other;
+        sum
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        let mut product = BigUint { limbs };
+        product.trim();
+        product
+    }
+}
+
+impl<'a> Sum<&'a BigUint> for BigUint {
+    fn sum<I: Iterator<Item = &'a BigUint>>(iter: I) -> BigUint {
+        let mut total = BigUint::zero();
+        for number in iter {
+            total += number;
+        }
+        total
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+        let mut total = BigUint::zero();
+        for number in iter {
+            total += &number;
+        }
+        total
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Error returned when parsing a string that isn't a decimal number.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseBigUintError;
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid decimal number")
+    }
+}
+
+impl Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    /// Parses a number written in decimal digits.
+    fn from_str(s: &str) -> Result<BigUint, ParseBigUintError> {
+        if s.is_empty() {
+            return Err(ParseBigUintError);
+        }
+        let mut number = BigUint::zero();
+        for digit in s.chars() {
+            let digit = digit.to_digit(10).ok_or(ParseBigUintError)?;
+            number.multiply_add_small(10, digit);
+        }
+        Ok(number)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.divide_small(CHUNK));
+        }
+        let mut digits = match chunks.pop() {
+            None => "0".to_string(),
+            Some(first) => first.to_string(),
+        };
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
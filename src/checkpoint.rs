@@ -0,0 +1,208 @@
+//! Resumable searches.
+//!
+//! A [`Checkpoint`] holds everything needed to carry on counting the solutions of a
+//! problem: the board it started from, the boards still waiting to be expanded, the
+//! pieces left to place on each of them, and the counters accumulated so far. It can be
+//! written to a file at any point and read back later, possibly on another machine, and
+//! the resumed search ends with the same count as an uninterrupted one.
+//!
+//! The search follows the duplicate-free order of [`crate::search`], so no set of
+//! already seen boards has to be saved alongside the frontier.
+
+use crate::bigint::BigUint;
+use crate::notation::{format_pieces, parse_pieces, ParseError};
+use crate::prune::ForwardChecker;
+use crate::{search, Board, ChessPiece};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+const HEADER: &str = "chess-checkpoint 2";
+
+/// The state of a search that can be saved and resumed.
+///
+/// # Fields
+///
+/// * `start` - The board the search started from, whose pieces may stand anywhere
+/// * `pieces` - All the pieces of the problem, in placement order
+/// * `frontier` - Boards still to be expanded, each with the index in `pieces` of the
+///   next piece to place on it. The last entry is expanded first.
+/// * `solutions` - Number of solutions found so far
+/// * `expanded` - Number of frontier entries expanded so far
+///
+/// # Example
+///
+/// ```
+/// use chess::checkpoint::Checkpoint;
+/// use chess::{Board, ChessPiece};
+///
+/// let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+/// let mut checkpoint = Checkpoint::new(Board::new(3, 3, Vec::new()), &pieces);
+/// while !checkpoint.run(2) {
+///     let mut saved = Vec::new();
+///     checkpoint.write(&mut saved).unwrap();
+///     checkpoint = Checkpoint::read(saved.as_slice()).unwrap();
+/// }
+/// assert_eq!(checkpoint.solutions.to_string(), "4");
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Checkpoint {
+    pub start: Board,
+    pub pieces: Vec<ChessPiece>,
+    pub frontier: Vec<(Board, usize)>,
+    pub solutions: BigUint,
+    pub expanded: u64,
+}
+
+impl Checkpoint {
+    /// Creates the checkpoint of a search that hasn't started yet.
+    pub fn new(board: Board, pieces: &[ChessPiece]) -> Checkpoint {
+        Checkpoint {
+            start: board.clone(),
+            pieces: pieces.to_vec(),
+            frontier: vec![(board, 0)],
+            solutions: BigUint::zero(),
+            expanded: 0,
+        }
+    }
+
+    /// Returns `true` once every board of the frontier has been expanded.
+    pub fn is_finished(&self) -> bool {
+        self.frontier.is_empty()
+    }
+
+    /// Expands at most `budget` frontier entries.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the search is finished, in which case `solutions` holds the final count
+    pub fn run(&mut self, budget: u64) -> bool {
+        if self.is_finished() {
+            return true;
+        }
+        let checker =
+            ForwardChecker::new(self.start.rows, self.start.cols).with_fixed(&self.start.pieces);
+        for _ in 0..budget {
+            let Some((board, next)) = self.frontier.pop() else {
+                break;
+            };
+            self.expanded += 1;
+            if next == self.pieces.len() {
+                self.solutions += 1u64;
+                continue;
+            }
+            let children = search::children(&board, &self.start.pieces, self.pieces[next]);
+            if next + 1 == self.pieces.len() {
+                self.solutions += children.len() as u64;
+            } else {
+                let tail = &self.pieces[next + 1..];
+                self.frontier.extend(
+                    children
+                        .into_iter()
+                        .rev()
+                        .filter(|child| checker.fits(child, tail))
+                        .map(|child| (child, next + 1)),
+                );
+            }
+        }
+        self.is_finished()
+    }
+
+    /// Calls `visit` once for every solution still to be found from the frontier, without
+    /// consuming it.
+    pub fn for_each_solution<F>(&self, mut visit: F)
+    where
+        F: FnMut(&Board),
+    {
+        for (board, next) in self.frontier.iter().rev() {
+            search::for_each_solution_from(
+                board,
+                &self.start.pieces,
+                &self.pieces[*next..],
+                &mut visit,
+            );
+        }
+    }
+
+    /// Writes the checkpoint in its text format.
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        writeln!(writer, "{}", HEADER)?;
+        writeln!(writer, "start {}", self.start)?;
+        writeln!(writer, "pieces {}", format_pieces(&self.pieces))?;
+        writeln!(writer, "solutions {}", self.solutions)?;
+        writeln!(writer, "expanded {}", self.expanded)?;
+        writeln!(writer, "frontier {}", self.frontier.len())?;
+        for (board, next) in &self.frontier {
+            writeln!(writer, "{} {}", next, board)?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a checkpoint written by [`Checkpoint::write`].
+    pub fn read<R: BufRead>(reader: R) -> io::Result<Checkpoint> {
+        let mut lines = reader.lines();
+        let mut next_line = || -> io::Result<String> {
+            lines
+                .next()
+                .unwrap_or_else(|| Err(ParseError::new("truncated checkpoint").into()))
+        };
+        if next_line()? != HEADER {
+            return Err(ParseError::new("not a checkpoint file").into());
+        }
+        let start = field(&next_line()?, "start")?.parse()?;
+        let pieces = parse_pieces(field(&next_line()?, "pieces")?)?;
+        let solutions = parse_number(&next_line()?, "solutions")?;
+        let expanded = parse_number(&next_line()?, "expanded")?;
+        let entries = parse_number(&next_line()?, "frontier")?;
+        let mut frontier = Vec::new();
+        for _ in 0..entries {
+            let line = next_line()?;
+            let (next, board) = line
+                .split_once(' ')
+                .ok_or_else(|| ParseError::new(format!("invalid frontier entry `{}`", line)))?;
+            let next: usize = next
+                .parse()
+                .map_err(|_| ParseError::new(format!("invalid frontier entry `{}`", line)))?;
+            if next > pieces.len() {
+                return Err(ParseError::new(format!("invalid frontier entry `{}`", line)).into());
+            }
+            frontier.push((board.parse()?, next));
+        }
+        Ok(Checkpoint {
+            start,
+            pieces,
+            frontier,
+            solutions,
+            expanded,
+        })
+    }
+
+    /// Saves the checkpoint to `path`.
+    ///
+    /// The checkpoint is first written next to `path` and then renamed over it, so an
+    /// interrupted save leaves the previous checkpoint intact.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        self.write(File::create(&temporary)?)?;
+        fs::rename(&temporary, path)
+    }
+
+    /// Loads a checkpoint saved with [`Checkpoint::save`].
+    pub fn load(path: &Path) -> io::Result<Checkpoint> {
+        Checkpoint::read(BufReader::new(File::open(path)?))
+    }
+}
+
+fn field<'a>(line: &'a str, name: &str) -> Result<&'a str, ParseError> {
+    line.strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix(' '))
+        .ok_or_else(|| ParseError::new(format!("expected `{}`, found `{}`", name, line)))
+}
+
+fn parse_number<T: std::str::FromStr>(line: &str, name: &str) -> Result<T, ParseError> {
+    field(line, name)?
+        .parse()
+        .map_err(|_| ParseError::new(format!("invalid {} in `{}`", name, line)))
+}
@@ -0,0 +1,286 @@
+//! DIMACS CNF encoding of problems, for cross-checking with SAT and #SAT solvers.
+//!
+//! Every square and piece type of the problem gets a variable that is true when a piece
+//! of that type stands on that square. Two families of clauses constrain them:
+//!
+//! * conflicts: for every two placements that share a square, or where one piece
+//!   attacks the other according to [`Piece::attacks`], at most one is true;
+//! * cardinality: exactly as many placements of each type are true as there are pieces
+//!   of that type. This uses a sequential counter whose auxiliary variables are defined
+//!   by equivalences, so they are fixed by the placements and every solution of the
+//!   problem corresponds to exactly one model. Model counts are therefore solution
+//!   counts.
+
+use crate::{Board, ChessPiece, Piece};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+/// A problem encoded as a formula in conjunctive normal form.
+///
+/// # Fields
+///
+/// * `rows` - The number of rows of the board
+/// * `cols` - The number of columns of the board
+/// * `types` - The piece types of the problem, in variable order
+/// * `variables` - The number of variables, placements and auxiliary ones
+/// * `clauses` - The clauses, as DIMACS literals
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Cnf {
+    pub rows: i8,
+    pub cols: i8,
+    pub types: Vec<ChessPiece>,
+    pub variables: usize,
+    pub clauses: Vec<Vec<i32>>,
+    counters: Vec<Counter>,
+}
+
+/// The auxiliary variables of the sequential counter of one piece type.
+///
+/// `first + (i - 1) * width + (j - 1)` is true when at least `j` of the first `i`
+/// placements of the type are true.
+#[derive(PartialEq, Eq, Clone, Debug)]
+struct Counter {
+    first: i32,
+    width: usize,
+}
+
+/// A literal whose value may already be known.
+#[derive(Clone, Copy)]
+enum Literal {
+    Constant(bool),
+    Variable(i32),
+}
+
+impl Literal {
+    fn negate(self) -> Literal {
+        match self {
+            Literal::Constant(value) => Literal::Constant(!value),
+            Literal::Variable(variable) => Literal::Variable(-variable),
+        }
+    }
+}
+
+/// Encodes the problem of placing `pieces` on an empty `rows`×`cols` board.
+///
+/// # Example
+///
+/// ```
+/// use chess::dimacs;
+/// use chess::ChessPiece;
+///
+/// let cnf = dimacs::encode(3, 3, &[ChessPiece::King, ChessPiece::King, ChessPiece::Rook]);
+/// assert!(cnf.to_dimacs().starts_with(&format!("p cnf {} ", cnf.variables)));
+/// ```
+pub fn encode(rows: i8, cols: i8, pieces: &[ChessPiece]) -> Cnf {
+    let mut types = pieces.to_vec();
+    types.sort();
+    types.dedup();
+    let squares = rows as usize * cols as usize;
+    let mut cnf = Cnf {
+        rows,
+        cols,
+        types,
+        variables: 0,
+        clauses: Vec::new(),
+        counters: Vec::new(),
+    };
+    cnf.variables = cnf.types.len() * squares;
+
+    let placements: Vec<Piece> = cnf.placements().collect();
+    for (i, &a) in placements.iter().enumerate() {
+        for &b in &placements[i + 1..] {
+            let same_square = (a.row, a.col) == (b.row, b.col);
+            if same_square || a.attacks(b) || b.attacks(a) {
+                cnf.clauses
+                    .push(vec![-cnf.variable(a).unwrap(), -cnf.variable(b).unwrap()]);
+            }
+        }
+    }
+
+    for t in 0..cnf.types.len() {
+        let chess_piece = cnf.types[t];
+        let count = pieces.iter().filter(|&&piece| piece == chess_piece).count();
+        let width = count + 1;
+        let counter = Counter {
+            first: cnf.variables as i32 + 1,
+            width,
+        };
+        cnf.variables += squares * width;
+        let at_least = |i: usize, j: usize| -> Literal {
+            if j == 0 {
+                Literal::Constant(true)
+            } else if i == 0 {
+                Literal::Constant(false)
+            } else {
+                Literal::Variable(counter.first + ((i - 1) * width + (j - 1)) as i32)
+            }
+        };
+        for i in 1..=squares {
+            let placed = Literal::Variable((t * squares + i) as i32);
+            for j in 1..=width {
+                // at_least(i, j) <=> at_least(i - 1, j) || (at_least(i - 1, j - 1) && placed)
+                let r = at_least(i, j);
+                let a = at_least(i - 1, j);
+                let b = at_least(i - 1, j - 1);
+                cnf.add_clause(&[a.negate(), r]);
+                cnf.add_clause(&[b.negate(), placed.negate(), r]);
+                cnf.add_clause(&[r.negate(), a, b]);
+                cnf.add_clause(&[r.negate(), a, placed]);
+            }
+        }
+        cnf.add_clause(&[at_least(squares, count)]);
+        cnf.add_clause(&[at_least(squares, count + 1).negate()]);
+        cnf.counters.push(counter);
+    }
+    cnf
+}
+
+impl Cnf {
+    /// Returns the variable that is true when `piece` stands on its square, or `None` if
+    /// the piece type isn't part of the problem or the square is off the board.
+    pub fn variable(&self, piece: Piece) -> Option<i32> {
+        let t = self
+            .types
+            .iter()
+            .position(|&chess_piece| chess_piece == piece.piece)?;
+        if !(1..=self.rows).contains(&piece.row) || !(1..=self.cols).contains(&piece.col) {
+            return None;
+        }
+        let square = (piece.row - 1) as usize * self.cols as usize + (piece.col - 1) as usize;
+        Some((t * self.squares() + square + 1) as i32)
+    }
+
+    /// Writes the formula in DIMACS CNF format.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_dimacs().as_bytes())?;
+        writer.flush()
+    }
+
+    /// Returns the formula in DIMACS CNF format.
+    ///
+    /// Comment lines map the placement variables back to pieces, e.g. `c 1 K(1,1)`.
+    pub fn to_dimacs(&self) -> String {
+        let mut dimacs = format!("p cnf {} {}\n", self.variables, self.clauses.len());
+        for piece in self.placements() {
+            let _ = writeln!(dimacs, "c {} {}", self.variable(piece).unwrap(), piece);
+        }
+        for clause in &self.clauses {
+            for literal in clause {
+                let _ = write!(dimacs, "{} ", literal);
+            }
+            dimacs.push_str("0\n");
+        }
+        dimacs
+    }
+
+    /// Maps a model back to the board it describes.
+    ///
+    /// `model` lists the true literals, in the form printed by SAT solvers; negative
+    /// literals and auxiliary variables are ignored.
+    pub fn decode(&self, model: &[i32]) -> Board {
+        let placements: Vec<Piece> = self.placements().collect();
+        let mut pieces: Vec<Piece> = model
+            .iter()
+            .filter(|&&literal| literal > 0 && (literal as usize) <= placements.len())
+            .map(|&literal| placements[literal as usize - 1])
+            .collect();
+        pieces.sort();
+        pieces.dedup();
+        Board::new(self.rows, self.cols, pieces)
+    }
+
+    /// Returns the complete assignment describing `board`, auxiliary variables included,
+    /// as a list of literals.
+    ///
+    /// The assignment satisfies the formula exactly when the board is a solution. Pieces
+    /// whose type isn't part of the problem have no variable and are left out.
+    pub fn model(&self, board: &Board) -> Vec<i32> {
+        let mut values = vec![false; self.variables + 1];
+        for &piece in &board.pieces {
+            if let Some(variable) = self.variable(piece) {
+                values[variable as usize] = true;
+            }
+        }
+        let squares = self.squares();
+        for (t, counter) in self.counters.iter().enumerate() {
+            let mut placed = 0;
+            for i in 1..=squares {
+                placed += values[t * squares + i] as usize;
+                for j in 1..=counter.width {
+                    let variable = counter.first as usize + (i - 1) * counter.width + (j - 1);
+                    values[variable] = placed >= j;
+                }
+            }
+        }
+        (1..=self.variables)
+            .map(|variable| {
+                if values[variable] {
+                    variable as i32
+                } else {
+                    -(variable as i32)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the literals of `model` satisfy every clause.
+    pub fn is_satisfied(&self, model: &[i32]) -> bool {
+        let mut values = vec![false; self.variables + 1];
+        for &literal in model {
+            if literal > 0 && (literal as usize) <= self.variables {
+                values[literal as usize] = true;
+            }
+        }
+        self.clauses.iter().all(|clause| {
+            clause
+                .iter()
+                .any(|&literal| values[literal.unsigned_abs() as usize] == (literal > 0))
+        })
+    }
+
+    fn squares(&self) -> usize {
+        self.rows as usize * self.cols as usize
+    }
+
+    /// Every placement of the problem, in variable order.
+    fn placements(&self) -> impl Iterator<Item = Piece> + '_ {
+        self.types.iter().flat_map(move |&piece| {
+            (1..=self.rows).flat_map(move |row| {
+                (1..=self.cols).map(move |col| Piece {
+                    row,
+                    col,
+                    piece,
+                    colour: None,
+                })
+            })
+        })
+    }
+
+    /// Adds a clause, leaving out false constants and dropping it if it holds a true one.
+    fn add_clause(&mut self, literals: &[Literal]) {
+        let mut clause = Vec::new();
+        for literal in literals {
+            match *literal {
+                Literal::Constant(true) => return,
+                Literal::Constant(false) => {}
+                Literal::Variable(variable) => clause.push(variable),
+            }
+        }
+        self.clauses.push(clause);
+    }
+}
+
+/// Parses the output of a SAT solver into a list of literals.
+///
+/// Both the competition format (`s SATISFIABLE` followed by `v` lines) and bare lists of
+/// literals are accepted. The terminating `0` and comment lines are ignored.
+pub fn parse_model(output: &str) -> Vec<i32> {
+    output
+        .lines()
+        .map(|line| line.strip_prefix('v').unwrap_or(line))
+        .filter(|line| !line.starts_with('s') && !line.starts_with('c'))
+        .flat_map(str::split_whitespace)
+        .filter_map(|literal| literal.parse().ok())
+        .filter(|&literal| literal != 0)
+        .collect()
+}
@@ -0,0 +1,504 @@
+//! Dancing Links backend.
+//!
+//! The problem is turned into an exact cover problem with colours (Knuth's Algorithm C,
+//! TAOCP 7.2.2.1), the generalisation of the Algorithm X formulation of the N-queens
+//! puzzle:
+//!
+//! * every piece to place is a primary item, and its options are the squares it may
+//!   stand on. When there are as many rooks (or queens) as rows, each row must hold one
+//!   of them, so the rows are the primary items of those pieces instead;
+//! * every square is a secondary item, so no two pieces share a square;
+//! * every row, column and diagonal is a secondary item. A piece that attacks along the
+//!   line takes it without a colour and a piece that merely stands on it takes it with
+//!   a colour, so pieces standing on the same line are compatible unless one of them
+//!   attacks along it;
+//! * every pair of squares a king (or a knight) move apart is a secondary item, taken
+//!   without a colour by a king (or knight) on either square and with a colour by any
+//!   other piece, which handles the leapers the same way;
+//! * every square a pawn may stand on paired with each square it attacks is a secondary
+//!   item, taken without a colour by a pawn on the first square and with a colour by
+//!   any piece on the second one. Pawns are placed without a colour, so they attack
+//!   towards row 1.
+//!
+//! Identical pieces are placed in increasing square order, so every solution is found
+//! exactly once.
+
+use crate::bigint::BigUint;
+use crate::{Board, ChessPiece, Piece};
+
+/// Colour given to secondary items taken by a piece that doesn't attack through them.
+const STANDING: i32 = 1;
+
+/// Counts the configurations obtained by placing all of `pieces` on `board`.
+///
+/// # Example
+///
+/// ```
+/// use chess::bigint::BigUint;
+/// use chess::{dlx, Board, ChessPiece};
+///
+/// let queens = [ChessPiece::Queen; 8];
+/// assert_eq!(dlx::count(&Board::new(8, 8, Vec::new()), &queens), BigUint::from(92));
+/// ```
+pub fn count(board: &Board, pieces: &[ChessPiece]) -> BigUint {
+    let mut total = 0u64;
+    for_each_solution(board, pieces, |_| total += 1);
+    BigUint::from(total)
+}
+
+/// Calls `visit` once for every configuration obtained by placing all of `pieces` on
+/// `board`. The pieces of the boards passed to `visit` are sorted.
+///
+/// Pieces already on `board` stay where they are; the new pieces may only go on squares
+/// where [`Board::is_safe`] allows them.
+pub fn for_each_solution<F>(board: &Board, pieces: &[ChessPiece], mut visit: F)
+where
+    F: FnMut(&Board),
+{
+    let mut links = Links::new(board, pieces);
+    let mut chosen = Vec::with_capacity(pieces.len());
+    links.search(&mut chosen, &mut |chosen: &[usize], links: &Links| {
+        let mut solution = board.clone();
+        solution
+            .pieces
+            .extend(chosen.iter().map(|&node| links.options[links.option[node]]));
+        solution.pieces.sort();
+        visit(&solution);
+    });
+}
+
+/// The dancing links of a problem, laid out as in Algorithm C: item headers first, then
+/// the nodes of every option, each option followed by a spacer node.
+struct Links {
+    llink: Vec<usize>,
+    rlink: Vec<usize>,
+    /// Item of a node, or for spacers the negated number of the option before them.
+    top: Vec<isize>,
+    ulink: Vec<usize>,
+    dlink: Vec<usize>,
+    color: Vec<i32>,
+    /// Number of options left in the column of an item.
+    len: Vec<usize>,
+    /// Option of every node.
+    option: Vec<usize>,
+    /// Placement made by every option.
+    options: Vec<Piece>,
+    /// For every primary item, the previous piece of the same type, if any.
+    previous: Vec<Option<usize>>,
+    /// Square of the placed pieces, indexed by primary item.
+    placed: Vec<Option<(i8, i8)>>,
+    /// The spacer ending the last option added.
+    last_spacer: usize,
+}
+
+impl Links {
+    fn new(board: &Board, pieces: &[ChessPiece]) -> Links {
+        let mut pieces = pieces.to_vec();
+        pieces.sort();
+        let (rows, cols) = (board.rows, board.cols);
+        let has = |kind: &[ChessPiece]| pieces.iter().any(|piece| kind.contains(piece));
+        let orthogonal = has(&[ChessPiece::Rook, ChessPiece::Queen]);
+        let diagonal = has(&[ChessPiece::Bishop, ChessPiece::Queen]);
+
+        // Secondary items, numbered after the primary ones.
+        let mut secondary: Vec<Secondary> = Vec::new();
+        for row in 1..=rows {
+            for col in 1..=cols {
+                secondary.push(Secondary::Square(row, col));
+            }
+        }
+        if orthogonal {
+            secondary.extend((1..=rows).map(Secondary::Row));
+            secondary.extend((1..=cols).map(Secondary::Col));
+        }
+        if diagonal {
+            secondary.extend((2 - cols..=rows - 1).map(Secondary::Diagonal));
+            secondary.extend((2..=rows + cols).map(Secondary::AntiDiagonal));
+        }
+        for (kind, moves) in [
+            (ChessPiece::King, &KING_STEPS[..]),
+            (ChessPiece::Knight, &KNIGHT_STEPS[..]),
+        ] {
+            if !has(&[kind]) {
+                continue;
+            }
+            for row in 1..=rows {
+                for col in 1..=cols {
+                    for &(dr, dc) in moves {
+                        let (other_row, other_col) = (row + dr, col + dc);
+                        if (1..=rows).contains(&other_row) && (1..=cols).contains(&other_col) {
+                            secondary.push(Secondary::Leap(
+                                kind,
+                                (row, col),
+                                (other_row, other_col),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if has(&[ChessPiece::Pawn]) {
+            for row in 2..=rows {
+                for col in 1..=cols {
+                    for other_col in [col - 1, col + 1] {
+                        if (1..=cols).contains(&other_col) {
+                            secondary
+                                .push(Secondary::PawnCapture((row, col), (row - 1, other_col)));
+                        }
+                    }
+                }
+            }
+        }
+
+        // A rook or queen type with one piece per row fills every row exactly once, so
+        // the rows themselves are its primary items, as in the usual N-queens cover.
+        // Every other piece is a primary item of its own.
+        let ranked = pieces.iter().copied().find(|&piece| {
+            orthogonal_attacker(piece)
+                && pieces.iter().filter(|&&other| other == piece).count() == rows as usize
+        });
+        let mut primaries: Vec<(ChessPiece, Option<i8>)> = Vec::new();
+        for &piece in &pieces {
+            if Some(piece) != ranked {
+                primaries.push((piece, None));
+            }
+        }
+        if let Some(piece) = ranked {
+            primaries.extend((1..=rows).map(|row| (piece, Some(row))));
+        }
+
+        let primary = primaries.len();
+        let items = primary + secondary.len();
+        let mut links = Links {
+            llink: vec![0; items + 2],
+            rlink: vec![0; items + 2],
+            top: (0..=items as isize).collect(),
+            ulink: (0..=items).collect(),
+            dlink: (0..=items).collect(),
+            color: vec![0; items + 1],
+            len: vec![0; items + 1],
+            option: vec![usize::MAX; items + 1],
+            options: Vec::new(),
+            previous: (0..primary)
+                .map(|i| {
+                    (i > 0 && primaries[i].1.is_none() && primaries[i - 1] == primaries[i])
+                        .then(|| i - 1)
+                })
+                .collect(),
+            placed: vec![None; primary],
+            last_spacer: items + 1,
+        };
+        // Primary items form the list headed by 0 and secondary items the list headed by
+        // items + 1, so that only primary items are ever chosen.
+        link_list(&mut links.llink, &mut links.rlink, 0, 1..=primary);
+        link_list(
+            &mut links.llink,
+            &mut links.rlink,
+            items + 1,
+            primary + 1..=items,
+        );
+
+        // The spacer before the first option.
+        links.top.push(0);
+        links.ulink.push(0);
+        links.dlink.push(0);
+        links.color.push(0);
+        links.option.push(usize::MAX);
+        for (slot, &(chess_piece, rank)) in primaries.iter().enumerate() {
+            for row in rank.map_or(1..=rows, |rank| rank..=rank) {
+                for col in 1..=cols {
+                    let piece = Piece {
+                        row,
+                        col,
+                        piece: chess_piece,
+                        colour: None,
+                    };
+                    if !board.is_safe(piece) {
+                        continue;
+                    }
+                    links.options.push(piece);
+                    let first = links.top.len();
+                    links.push_node(slot + 1, 0);
+                    for (index, item) in secondary.iter().enumerate() {
+                        if let Some(color) = item.color(piece) {
+                            links.push_node(primary + 1 + index, color);
+                        }
+                    }
+                    links.push_spacer(first);
+                }
+            }
+        }
+        links
+    }
+
+    /// Appends a node of `item` to the option being built.
+    fn push_node(&mut self, item: usize, color: i32) {
+        let node = self.top.len();
+        let last = self.ulink[item];
+        self.top.push(item as isize);
+        self.ulink.push(last);
+        self.dlink.push(item);
+        self.color.push(color);
+        self.option.push(self.options.len() - 1);
+        self.dlink[last] = node;
+        self.ulink[item] = node;
+        self.len[item] += 1;
+    }
+
+    /// Ends the option whose first node is `first` with a spacer.
+    ///
+    /// A spacer points up to the first node of the option before it and down to the last
+    /// node of the option after it.
+    fn push_spacer(&mut self, first: usize) {
+        let node = self.top.len();
+        self.top.push(-(self.options.len() as isize));
+        self.ulink.push(first);
+        self.dlink.push(0);
+        self.color.push(0);
+        self.option.push(usize::MAX);
+        self.dlink[self.last_spacer] = node - 1;
+        self.last_spacer = node;
+    }
+
+    fn search<F>(&mut self, chosen: &mut Vec<usize>, visit: &mut F)
+    where
+        F: FnMut(&[usize], &Links),
+    {
+        let Some(item) = self.choose() else {
+            visit(chosen, self);
+            return;
+        };
+        if self.len[item] == 0 {
+            return;
+        }
+        let slot = item - 1;
+        let after = self.previous[slot].and_then(|previous| self.placed[previous]);
+        self.cover(item);
+        let mut node = self.dlink[item];
+        while node != item {
+            let piece = self.options[self.option[node]];
+            if after.is_none_or(|after| (piece.row, piece.col) > after) {
+                self.commit_option(node);
+                self.placed[slot] = Some((piece.row, piece.col));
+                chosen.push(node);
+                self.search(chosen, visit);
+                chosen.pop();
+                self.placed[slot] = None;
+                self.uncommit_option(node);
+            }
+            node = self.dlink[node];
+        }
+        self.uncover(item);
+    }
+
+    /// Chooses the piece with the fewest options among the first unplaced piece of every
+    /// type, or returns `None` when every piece is placed.
+    fn choose(&self) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        let mut item = self.rlink[0];
+        while item != 0 {
+            let eligible =
+                self.previous[item - 1].is_none_or(|previous| self.placed[previous].is_some());
+            if eligible && best.is_none_or(|best| self.len[item] < self.len[best]) {
+                best = Some(item);
+            }
+            item = self.rlink[item];
+        }
+        best
+    }
+
+    fn commit_option(&mut self, node: usize) {
+        let mut p = node + 1;
+        while p != node {
+            let item = self.top[p];
+            if item <= 0 {
+                p = self.ulink[p];
+            } else {
+                self.commit(p, item as usize);
+                p += 1;
+            }
+        }
+    }
+
+    fn uncommit_option(&mut self, node: usize) {
+        let mut p = node - 1;
+        while p != node {
+            let item = self.top[p];
+            if item <= 0 {
+                p = self.dlink[p];
+            } else {
+                self.uncommit(p, item as usize);
+                p -= 1;
+            }
+        }
+    }
+
+    fn commit(&mut self, node: usize, item: usize) {
+        if self.color[node] == 0 {
+            self.cover(item);
+        } else if self.color[node] > 0 {
+            self.purify(node);
+        }
+    }
+
+    fn uncommit(&mut self, node: usize, item: usize) {
+        if self.color[node] == 0 {
+            self.uncover(item);
+        } else if self.color[node] > 0 {
+            self.unpurify(node);
+        }
+    }
+
+    fn cover(&mut self, item: usize) {
+        let mut p = self.dlink[item];
+        while p != item {
+            self.hide(p);
+            p = self.dlink[p];
+        }
+        let (l, r) = (self.llink[item], self.rlink[item]);
+        self.rlink[l] = r;
+        self.llink[r] = l;
+    }
+
+    fn uncover(&mut self, item: usize) {
+        let (l, r) = (self.llink[item], self.rlink[item]);
+        self.rlink[l] = item;
+        self.llink[r] = item;
+        let mut p = self.ulink[item];
+        while p != item {
+            self.unhide(p);
+            p = self.ulink[p];
+        }
+    }
+
+    fn hide(&mut self, node: usize) {
+        let mut q = node + 1;
+        while q != node {
+            let item = self.top[q];
+            if item <= 0 {
+                q = self.ulink[q];
+            } else if self.color[q] < 0 {
+                q += 1;
+            } else {
+                let (u, d) = (self.ulink[q], self.dlink[q]);
+                self.dlink[u] = d;
+                self.ulink[d] = u;
+                self.len[item as usize] -= 1;
+                q += 1;
+            }
+        }
+    }
+
+    fn unhide(&mut self, node: usize) {
+        let mut q = node - 1;
+        while q != node {
+            let item = self.top[q];
+            if item <= 0 {
+                q = self.dlink[q];
+            } else if self.color[q] < 0 {
+                q -= 1;
+            } else {
+                let (u, d) = (self.ulink[q], self.dlink[q]);
+                self.dlink[u] = q;
+                self.ulink[d] = q;
+                self.len[item as usize] += 1;
+                q -= 1;
+            }
+        }
+    }
+
+    fn purify(&mut self, node: usize) {
+        let color = self.color[node];
+        let item = self.top[node] as usize;
+        let mut q = self.dlink[item];
+        while q != item {
+            if self.color[q] == color {
+                self.color[q] = -1;
+            } else {
+                self.hide(q);
+            }
+            q = self.dlink[q];
+        }
+    }
+
+    fn unpurify(&mut self, node: usize) {
+        let color = self.color[node];
+        let item = self.top[node] as usize;
+        let mut q = self.ulink[item];
+        while q != item {
+            if self.color[q] < 0 {
+                self.color[q] = color;
+            } else {
+                self.unhide(q);
+            }
+            q = self.ulink[q];
+        }
+    }
+}
+
+/// Links `members` into a circular doubly linked list headed by `head`.
+fn link_list(
+    llink: &mut [usize],
+    rlink: &mut [usize],
+    head: usize,
+    members: std::ops::RangeInclusive<usize>,
+) {
+    let mut last = head;
+    for member in members {
+        rlink[last] = member;
+        llink[member] = last;
+        last = member;
+    }
+    rlink[last] = head;
+    llink[head] = last;
+}
+
+const KING_STEPS: [(i8, i8); 4] = [(0, 1), (1, -1), (1, 0), (1, 1)];
+const KNIGHT_STEPS: [(i8, i8); 4] = [(1, -2), (1, 2), (2, -1), (2, 1)];
+
+/// A secondary item: something at most one piece may claim without a colour.
+enum Secondary {
+    Square(i8, i8),
+    Row(i8),
+    Col(i8),
+    /// Squares with the same `row - col`.
+    Diagonal(i8),
+    /// Squares with the same `row + col`.
+    AntiDiagonal(i8),
+    /// Two squares a king or knight move apart.
+    Leap(ChessPiece, (i8, i8), (i8, i8)),
+    /// The square of a pawn and a square it attacks.
+    PawnCapture((i8, i8), (i8, i8)),
+}
+
+impl Secondary {
+    /// Returns the colour `piece` takes this item with, `0` meaning no colour, or `None`
+    /// if the piece doesn't take it.
+    fn color(&self, piece: Piece) -> Option<i32> {
+        let square = (piece.row, piece.col);
+        let (on, attacks_along) = match *self {
+            Secondary::Square(row, col) => (square == (row, col), true),
+            Secondary::Row(row) => (piece.row == row, orthogonal_attacker(piece.piece)),
+            Secondary::Col(col) => (piece.col == col, orthogonal_attacker(piece.piece)),
+            Secondary::Diagonal(d) => (piece.row - piece.col == d, diagonal_attacker(piece.piece)),
+            Secondary::AntiDiagonal(d) => {
+                (piece.row + piece.col == d, diagonal_attacker(piece.piece))
+            }
+            Secondary::Leap(kind, a, b) => (square == a || square == b, piece.piece == kind),
+            Secondary::PawnCapture(from, to) => {
+                let attacking = square == from && piece.piece == ChessPiece::Pawn;
+                (attacking || square == to, attacking)
+            }
+        };
+        on.then_some(if attacks_along { 0 } else { STANDING })
+    }
+}
+
+fn orthogonal_attacker(piece: ChessPiece) -> bool {
+    matches!(piece, ChessPiece::Rook | ChessPiece::Queen)
+}
+
+fn diagonal_attacker(piece: ChessPiece) -> bool {
+    matches!(piece, ChessPiece::Bishop | ChessPiece::Queen)
+}
@@ -0,0 +1,89 @@
+//! Approximate counting for problems too large to enumerate.
+//!
+//! Knuth's estimator walks a single random path down the search tree of
+//! [`crate::search`], choosing each child uniformly. If the path reaches a solution, the
+//! product of the number of children met along the way is an unbiased estimate of the
+//! number of solutions; if it dead-ends, the estimate is zero. Averaging many such probes
+//! gives an estimate whose spread is measured from the probes themselves.
+
+use crate::rng::Rng;
+use crate::{search, Board, ChessPiece};
+
+/// Standard normal quantile for a two-sided 95% confidence interval.
+const Z_95: f64 = 1.959_963_984_540_054;
+
+/// An estimate of the number of solutions of a problem.
+///
+/// # Fields
+///
+/// * `count` - The estimated number of solutions
+/// * `standard_error` - The standard error of `count`
+/// * `lower` - Lower bound of the 95% confidence interval, never below zero
+/// * `upper` - Upper bound of the 95% confidence interval
+/// * `probes` - Number of random paths the estimate is based on
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Estimate {
+    pub count: f64,
+    pub standard_error: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub probes: u64,
+}
+
+/// Estimates the number of configurations obtained by placing `pieces` on `board`.
+///
+/// The confidence interval relies on the normal approximation, so it is only meaningful
+/// once enough probes have reached a solution. The same `seed` gives the same estimate.
+///
+/// # Example
+///
+/// ```
+/// use chess::{estimate, Board, ChessPiece};
+///
+/// let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+/// let estimate = estimate::estimate(&Board::new(3, 3, Vec::new()), &pieces, 1000, 1);
+/// assert_eq!(estimate.probes, 1000);
+/// assert!(estimate.lower <= 4.0 && 4.0 <= estimate.upper);
+/// ```
+pub fn estimate(board: &Board, pieces: &[ChessPiece], probes: u64, seed: u64) -> Estimate {
+    let mut rng = Rng::new(seed);
+    let mut mean = 0.0;
+    let mut squares = 0.0;
+    for probe in 1..=probes {
+        let value = probe_once(board, pieces, &mut rng);
+        // Welford's update keeps the variance accurate for very large values.
+        let delta = value - mean;
+        mean += delta / probe as f64;
+        squares += delta * (value - mean);
+    }
+    let variance = if probes > 1 {
+        squares / (probes - 1) as f64
+    } else {
+        0.0
+    };
+    let standard_error = (variance / probes.max(1) as f64).sqrt();
+    Estimate {
+        count: mean,
+        standard_error,
+        lower: (mean - Z_95 * standard_error).max(0.0),
+        upper: mean + Z_95 * standard_error,
+        probes,
+    }
+}
+
+/// Walks one random path of the search tree and returns its estimate.
+fn probe_once(board: &Board, pieces: &[ChessPiece], rng: &mut Rng) -> f64 {
+    let fixed = &board.pieces;
+    let mut board = board.clone();
+    let mut weight = 1.0;
+    for &chess_piece in pieces {
+        let mut children = search::children(&board, fixed, chess_piece);
+        if children.is_empty() {
+            return 0.0;
+        }
+        weight *= children.len() as f64;
+        let chosen = rng.below(children.len() as u64) as usize;
+        board = children.swap_remove(chosen);
+    }
+    weight
+}
@@ -0,0 +1,188 @@
+//! Where each type of piece stands across the solutions of a problem.
+//!
+//! A [`Heatmap`] counts, for every piece type and square, the solutions with a piece of
+//! that type on that square. It renders as text, one character per square from ` ` (in
+//! no solution) to `@` (in the most solutions for that type), and as an SVG image with
+//! one shaded grid per type.
+
+use crate::{search, Board, ChessPiece};
+use std::fmt::{self, Write};
+
+/// Shades of the text rendering, from least to most often occupied.
+const SHADES: &[u8] = b" .:-=+*#%@";
+
+/// Side of a square in the SVG rendering, in pixels.
+const SQUARE: usize = 24;
+
+/// Per-square counts of each piece type over a set of solutions.
+///
+/// # Example
+///
+/// ```
+/// use chess::heatmap::Heatmap;
+/// use chess::{Board, ChessPiece};
+///
+/// let board = Board::new(3, 3, Vec::new());
+/// let heatmap = Heatmap::of(&board, &[ChessPiece::King, ChessPiece::King, ChessPiece::Rook]);
+/// assert_eq!(heatmap.solutions, 4);
+/// // The rook is in the middle of an edge in every solution, the kings in the corners.
+/// assert_eq!(heatmap.count(ChessPiece::Rook, 1, 2), 1);
+/// assert_eq!(heatmap.count(ChessPiece::King, 1, 1), 2);
+/// assert_eq!(heatmap.count(ChessPiece::King, 2, 2), 0);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Heatmap {
+    pub rows: i8,
+    pub cols: i8,
+    /// The number of solutions counted.
+    pub solutions: u64,
+    /// `counts[type][square]`, with squares in row-major order.
+    counts: [Vec<u64>; ChessPiece::ALL.len()],
+}
+
+impl Heatmap {
+    /// Creates a heatmap of `rows`×`cols` boards with no solutions counted yet.
+    pub fn new(rows: i8, cols: i8) -> Heatmap {
+        let squares = rows as usize * cols as usize;
+        Heatmap {
+            rows,
+            cols,
+            solutions: 0,
+            counts: std::array::from_fn(|_| vec![0; squares]),
+        }
+    }
+
+    /// Counts every solution of placing `pieces` on `board`.
+    pub fn of(board: &Board, pieces: &[ChessPiece]) -> Heatmap {
+        let mut heatmap = Heatmap::new(board.rows, board.cols);
+        search::for_each_solution(board, pieces, |solution| heatmap.add(solution));
+        heatmap
+    }
+
+    /// Counts one more solution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `solution` is not the size of the heatmap.
+    pub fn add(&mut self, solution: &Board) {
+        assert_eq!(
+            (solution.rows, solution.cols),
+            (self.rows, self.cols),
+            "the solution is not the size of the heatmap"
+        );
+        for piece in &solution.pieces {
+            let square = (piece.row - 1) as usize * self.cols as usize + (piece.col - 1) as usize;
+            self.counts[piece.piece as usize][square] += 1;
+        }
+        self.solutions += 1;
+    }
+
+    /// Returns the number of solutions with a piece of type `chess_piece` at `row`, `col`.
+    pub fn count(&self, chess_piece: ChessPiece, row: i8, col: i8) -> u64 {
+        self.counts[chess_piece as usize]
+            [(row - 1) as usize * self.cols as usize + (col - 1) as usize]
+    }
+
+    /// Returns the counts of `chess_piece`, one row of the board at a time.
+    pub fn grid(&self, chess_piece: ChessPiece) -> Vec<Vec<u64>> {
+        self.counts[chess_piece as usize]
+            .chunks(self.cols as usize)
+            .map(<[u64]>::to_vec)
+            .collect()
+    }
+
+    /// Returns the piece types found in the solutions, in declaration order.
+    pub fn types(&self) -> Vec<ChessPiece> {
+        ChessPiece::ALL
+            .into_iter()
+            .filter(|&chess_piece| {
+                self.counts[chess_piece as usize]
+                    .iter()
+                    .any(|&count| count > 0)
+            })
+            .collect()
+    }
+
+    /// Renders one grid per piece type as an SVG image, darker squares holding the type
+    /// in more solutions. Hovering over a square shows its count.
+    pub fn to_svg(&self) -> String {
+        let types = self.types();
+        let (rows, cols) = (self.rows as usize, self.cols as usize);
+        // Each grid has a caption line above it and a square of space after it.
+        let width = (types.len() * (cols + 1)).max(1) * SQUARE;
+        let height = (rows + 1) * SQUARE;
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif" font-size="{}">"#,
+            width,
+            height,
+            SQUARE * 2 / 3
+        );
+        for (i, &chess_piece) in types.iter().enumerate() {
+            let left = i * (cols + 1) * SQUARE;
+            let max = self.max(chess_piece);
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}">{}</text>"#,
+                left,
+                SQUARE * 3 / 4,
+                chess_piece
+            );
+            for (row, counts) in self.grid(chess_piece).iter().enumerate() {
+                for (col, &count) in counts.iter().enumerate() {
+                    let _ = writeln!(
+                        svg,
+                        r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#b22222" fill-opacity="{:.3}" stroke="#cccccc"><title>{}({},{}): {}</title></rect>"##,
+                        left + col * SQUARE,
+                        (row + 1) * SQUARE,
+                        SQUARE,
+                        SQUARE,
+                        count as f64 / max as f64,
+                        chess_piece,
+                        row + 1,
+                        col + 1,
+                        count
+                    );
+                }
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Returns the highest count of `chess_piece`, at least 1 so that it can divide.
+    fn max(&self, chess_piece: ChessPiece) -> u64 {
+        self.counts[chess_piece as usize]
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1)
+    }
+}
+
+impl fmt::Display for Heatmap {
+    /// Writes a grid per piece type, each preceded by the type and the highest count,
+    /// which `@` stands for.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{} {} solutions",
+            self.rows, self.cols, self.solutions
+        )?;
+        for chess_piece in self.types() {
+            let max = self.max(chess_piece);
+            write!(f, "\n\n{} (@ = {})", chess_piece, max)?;
+            for counts in self.grid(chess_piece) {
+                f.write_char('\n')?;
+                for count in counts {
+                    // Any count above zero gets at least the lightest visible shade.
+                    let shade = (count * (SHADES.len() as u64 - 1)).div_ceil(max);
+                    f.write_char(SHADES[shade as usize] as char)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
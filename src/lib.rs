@@ -23,9 +23,40 @@
 //! println!("Found {} solutions", solutions.len());
 //! ```
 
+use crate::prune::ForwardChecker;
+use crate::rules::{PairRule, Rules};
+use crate::stats::{Recorder, SearchStats, Sink};
 use std::collections::{HashSet, VecDeque};
 use std::rc::Rc;
 
+pub mod armies;
+pub mod backend;
+pub mod bench;
+pub mod bigint;
+pub mod binary;
+pub mod checkpoint;
+pub mod dimacs;
+pub mod dlx;
+pub mod estimate;
+pub mod heatmap;
+pub mod notation;
+pub mod oeis;
+pub mod oracle;
+pub mod order;
+pub mod packed;
+pub mod prune;
+pub mod rng;
+pub mod rules;
+pub mod sample;
+pub mod search;
+pub mod shard;
+pub mod squares;
+pub mod stats;
+pub mod store;
+pub mod sweep;
+pub mod transfer;
+pub mod validate;
+
 /// Represents the different types of chess pieces used in the puzzle.
 ///
 /// Each piece type has unique movement and attack patterns:
@@ -34,6 +65,8 @@ use std::rc::Rc;
 /// - `Knight`: Attacks in an L-shape (2 squares in one direction, 1 in perpendicular)
 /// - `Queen`: Attacks horizontally, vertically, and diagonally
 /// - `King`: Attacks all adjacent squares (one square in any direction)
+/// - `Pawn`: Attacks the two squares diagonally in front of it, in the direction given by
+///   its colour
 #[derive(PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, Debug)]
 pub enum ChessPiece {
     Rook,
@@ -41,6 +74,30 @@ pub enum ChessPiece {
     Knight,
     Queen,
     King,
+    Pawn,
+}
+
+impl ChessPiece {
+    /// Every piece type, in declaration order: tables indexed by piece type use
+    /// `piece as usize` as the index.
+    pub(crate) const ALL: [ChessPiece; 6] = [
+        ChessPiece::Rook,
+        ChessPiece::Bishop,
+        ChessPiece::Knight,
+        ChessPiece::Queen,
+        ChessPiece::King,
+        ChessPiece::Pawn,
+    ];
+}
+
+/// The colour of a piece, for problems where pieces belong to opposing armies.
+///
+/// White starts at the bottom of the board (its first rank is the last row) and moves
+/// up, black starts at the top and moves down.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, Debug)]
+pub enum Colour {
+    White,
+    Black,
 }
 
 /// Represents a chess piece placed on a specific position on the board.
@@ -54,12 +111,21 @@ pub enum ChessPiece {
 /// * `row` - The row position (1-indexed, 1 ≤ row ≤ board_rows)
 /// * `col` - The column position (1-indexed, 1 ≤ col ≤ board_cols)
 /// * `piece` - The type of chess piece (King, Queen, Rook, Bishop, or Knight)
+/// * `colour` - The army the piece belongs to, or `None` for the colourless pieces of
+///   the original puzzle
 #[derive(PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, Debug)]
 pub struct Piece {
     pub row: i8,
     pub col: i8,
     pub piece: ChessPiece,
+    pub colour: Option<Colour>,
 }
+/// The largest number of rows or columns of a board the solvers accept.
+///
+/// Coordinates are `i8`, and the solvers add a row to a column or a move to a square, so
+/// the sum of two coordinates has to fit.
+pub const MAX_SIZE: i8 = 63;
+
 /// Represents a chess board configuration with its dimensions and placed pieces.
 ///
 /// A board tracks its size (rows × cols) and all pieces currently placed on it.
@@ -84,7 +150,7 @@ pub struct Board {
 
 impl Piece {
     fn knight_moves(self, chess_piece: Piece) -> bool {
-        const KNIGHT_MOVES: [(i8, i8); 9] = [
+        const KNIGHT_MOVES: [(i16, i16); 9] = [
             (1, -2),
             (2, -1),
             (2, 1),
@@ -96,14 +162,14 @@ impl Piece {
             (-1, -2),
         ];
         KNIGHT_MOVES.iter().any(|(x_offset, y_offset)| {
-            let dest_row = self.row + x_offset;
-            let dest_col = self.col + y_offset;
-            dest_row == chess_piece.row && dest_col == chess_piece.col
+            let dest_row = i16::from(self.row) + x_offset;
+            let dest_col = i16::from(self.col) + y_offset;
+            dest_row == i16::from(chess_piece.row) && dest_col == i16::from(chess_piece.col)
         })
     }
 
     fn king_moves(self, chess_piece: Piece) -> bool {
-        const KING_MOVES: [(i8, i8); 9] = [
+        const KING_MOVES: [(i16, i16); 9] = [
             (-1, -1),
             (-1, 0),
             (-1, 1),
@@ -115,12 +181,24 @@ impl Piece {
             (1, 1),
         ];
         KING_MOVES.iter().any(|(x_offset, y_offset)| {
-            let dest_row = self.row + x_offset;
-            let dest_col = self.col + y_offset;
-            dest_row == chess_piece.row && dest_col == chess_piece.col
+            let dest_row = i16::from(self.row) + x_offset;
+            let dest_col = i16::from(self.col) + y_offset;
+            dest_row == i16::from(chess_piece.row) && dest_col == i16::from(chess_piece.col)
         })
     }
 
+    fn pawn_moves(self, chess_piece: Piece) -> bool {
+        // Colourless pawns move like white ones, towards row 1.
+        let forward = match self.colour {
+            Some(Colour::Black) => 1,
+            _ => -1,
+        };
+        let same_square = self.row == chess_piece.row && self.col == chess_piece.col;
+        let diagonal_forward = i16::from(chess_piece.row) == i16::from(self.row) + forward
+            && distance(chess_piece.col, self.col) == 1;
+        same_square || diagonal_forward
+    }
+
     /// Determines whether this piece can attack another piece based on chess movement rules.
     ///
     /// Each piece type has different attack patterns:
@@ -129,6 +207,8 @@ impl Piece {
     /// - **Knight**: Can attack pieces that are 2 squares away in one direction and 1 square perpendicular
     /// - **Queen**: Combines Rook and Bishop (same row, column, or diagonal)
     /// - **King**: Can attack any piece in the 8 adjacent squares
+    /// - **Pawn**: Can attack the two squares diagonally forward, towards row 1 for white
+    ///   and colourless pawns and towards the last row for black ones
     ///
     /// # Arguments
     ///
@@ -143,27 +223,34 @@ impl Piece {
     /// ```
     /// use chess::{Piece, ChessPiece};
     ///
-    /// let rook = Piece { row: 1, col: 1, piece: ChessPiece::Rook };
-    /// let target = Piece { row: 1, col: 5, piece: ChessPiece::King };
+    /// let rook = Piece { row: 1, col: 1, piece: ChessPiece::Rook, colour: None };
+    /// let target = Piece { row: 1, col: 5, piece: ChessPiece::King, colour: None };
     /// assert!(rook.attacks(target)); // Rook attacks along the same row
     /// ```
     pub fn attacks(self, chess_piece: Piece) -> bool {
         match self.piece {
             ChessPiece::Rook => self.row == chess_piece.row || self.col == chess_piece.col,
             ChessPiece::Bishop => {
-                i8::abs(self.row - chess_piece.row) == i8::abs(self.col - chess_piece.col)
+                distance(self.row, chess_piece.row) == distance(self.col, chess_piece.col)
             }
             ChessPiece::Knight => Self::knight_moves(self, chess_piece),
             ChessPiece::Queen => {
                 self.row == chess_piece.row
                     || self.col == chess_piece.col
-                    || i8::abs(self.row - chess_piece.row) == i8::abs(self.col - chess_piece.col)
+                    || distance(self.row, chess_piece.row) == distance(self.col, chess_piece.col)
             }
             ChessPiece::King => Self::king_moves(self, chess_piece),
+            ChessPiece::Pawn => Self::pawn_moves(self, chess_piece),
         }
     }
 }
 
+/// Returns the distance between two rows or two columns, computed in a wider type so that
+/// no coordinate overflows.
+fn distance(a: i8, b: i8) -> i16 {
+    i16::abs(i16::from(a) - i16::from(b))
+}
+
 impl Board {
     /// Creates a new chess board with the specified dimensions and pieces.
     ///
@@ -193,7 +280,10 @@ impl Board {
     /// Checks whether a piece can be safely placed without attacking or being attacked.
     ///
     /// A position is considered safe if the piece to be placed neither attacks any existing
-    /// piece on the board, nor is attacked by any existing piece.
+    /// piece on the board, nor is attacked by any existing piece. Pieces of the same colour
+    /// are left out, as [`PairRule::OppositeColours`] has it, but never share a square.
+    /// This is the rule of [`Rules::default`]; see [`Board::is_safe_with`] for the
+    /// variants.
     ///
     /// # Arguments
     ///
@@ -209,13 +299,82 @@ impl Board {
     /// use chess::{Board, Piece, ChessPiece};
     ///
     /// let board = Board::new(3, 3, Vec::new());
-    /// let piece = Piece { row: 1, col: 1, piece: ChessPiece::Rook };
+    /// let piece = Piece { row: 1, col: 1, piece: ChessPiece::Rook, colour: None };
     /// assert!(board.is_safe(piece)); // Safe on an empty board
     /// ```
     pub fn is_safe(&self, chess_piece: Piece) -> bool {
-        self.pieces
+        self.pieces.iter().all(|&piece| {
+            if PairRule::default().applies(piece, chess_piece) {
+                !piece.attacks(chess_piece) && !chess_piece.attacks(piece)
+            } else {
+                (piece.row, piece.col) != (chess_piece.row, chess_piece.col)
+            }
+        })
+    }
+
+    /// Checks whether a piece can be safely placed under the given rules.
+    ///
+    /// Attacks that `rules` leaves out, because of the colours of the two pieces or of
+    /// their types, are allowed, but two pieces never share a square. Pawns may be kept
+    /// off the first and last rows. When the rules allow each piece up to `k` attackers,
+    /// the piece is safe if it has at most `k` attackers on the board and none of the
+    /// pieces it attacks would get more.
+    ///
+    /// # Arguments
+    ///
+    /// * `chess_piece` - The piece to check for safe placement
+    /// * `rules` - The rules deciding which attacks are forbidden, and where pawns may
+    ///   stand
+    ///
+    /// # Returns
+    ///
+    /// `true` if the piece can be safely placed, `false` otherwise
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::rules::{PairRule, Rules};
+    /// use chess::{Board, ChessPiece, Colour, Piece};
+    ///
+    /// let white = Piece { row: 1, col: 1, piece: ChessPiece::Queen, colour: Some(Colour::White) };
+    /// let board = Board::new(3, 3, vec![white]);
+    /// let rules = Rules { pairs: PairRule::OppositeColours, ..Rules::default() };
+    /// assert!(board.is_safe_with(Piece { row: 1, col: 3, ..white }, &rules));
+    /// let black = Piece { row: 1, col: 3, colour: Some(Colour::Black), ..white };
+    /// assert!(!board.is_safe_with(black, &rules));
+    /// ```
+    pub fn is_safe_with(&self, chess_piece: Piece, rules: &Rules) -> bool {
+        if !rules.may_stand(chess_piece, self.rows) {
+            return false;
+        }
+        if rules.forbids_every_attack() {
+            return self.is_safe(chess_piece);
+        }
+        let square = (chess_piece.row, chess_piece.col);
+        if self
+            .pieces
             .iter()
-            .all(|&piece| !piece.attacks(chess_piece) && !chess_piece.attacks(piece))
+            .any(|piece| (piece.row, piece.col) == square)
+        {
+            return false;
+        }
+        if rules.max_attackers > 0 {
+            let attackers = |victim: Piece| {
+                self.pieces
+                    .iter()
+                    .filter(|&&piece| rules.threatens(piece, victim))
+                    .count()
+            };
+            return attackers(chess_piece) <= rules.max_attackers
+                && self
+                    .pieces
+                    .iter()
+                    .filter(|&&victim| rules.threatens(chess_piece, victim))
+                    .all(|&victim| attackers(victim) < rules.max_attackers);
+        }
+        self.pieces.iter().all(|&piece| {
+            !rules.threatens(piece, chess_piece) && !rules.threatens(chess_piece, piece)
+        })
     }
 
     /// Creates a new board with the given piece added to it.
@@ -243,7 +402,7 @@ impl Board {
     /// use chess::{Board, Piece, ChessPiece};
     ///
     /// let board = Board::new(3, 3, Vec::new());
-    /// let piece = Piece { row: 1, col: 1, piece: ChessPiece::King };
+    /// let piece = Piece { row: 1, col: 1, piece: ChessPiece::King, colour: None };
     /// let new_board = board.place(piece);
     /// assert_eq!(new_board.pieces.len(), 1);
     /// ```
@@ -315,32 +474,87 @@ pub fn solution<'a>(
     solutions: &'a mut HashSet<Rc<Board>>,
     tested_configurations: &mut HashSet<Rc<Board>>,
 ) -> &'a HashSet<Rc<Board>> {
+    search_with(board_stack, solutions, tested_configurations, &mut ())
+}
+
+/// Like [`solution`], also returning statistics on the search.
+///
+/// The boards are expanded in order of depth, so the time of each depth is the time
+/// between the first board of that depth leaving `board_stack` and the first board of
+/// the next one.
+pub fn solution_with_stats<'a>(
+    board_stack: &mut VecDeque<(Rc<Board>, &[ChessPiece])>,
+    solutions: &'a mut HashSet<Rc<Board>>,
+    tested_configurations: &mut HashSet<Rc<Board>>,
+) -> (&'a HashSet<Rc<Board>>, SearchStats) {
+    let mut recorder = Recorder::new(board_stack);
+    let solutions = search_with(board_stack, solutions, tested_configurations, &mut recorder);
+    (solutions, recorder.finish())
+}
+
+/// The search behind [`solution`] and [`solution_with_stats`], reporting to `sink`.
+fn search_with<'a, S: Sink>(
+    board_stack: &mut VecDeque<(Rc<Board>, &[ChessPiece])>,
+    solutions: &'a mut HashSet<Rc<Board>>,
+    tested_configurations: &mut HashSet<Rc<Board>>,
+    sink: &mut S,
+) -> &'a HashSet<Rc<Board>> {
+    let mut checker: Option<ForwardChecker> = None;
+    // The pieces of the starting boards, which don't follow the order of the search.
+    let fixed: Vec<Piece> = board_stack
+        .iter()
+        .flat_map(|(board, _)| board.pieces.iter().copied())
+        .collect();
     while !board_stack.is_empty() {
         match board_stack.pop_back() {
             None => panic!("Board stack is empty!"),
             Some((board_rc, pieces)) => {
+                if pieces.is_empty() {
+                    // Nothing is left to place: the board is a solution as it stands.
+                    solutions.insert(board_rc);
+                    continue;
+                }
                 let board = &*board_rc;
+                sink.expand(board);
+                let checker = match checker {
+                    Some(ref checker) if checker.dimensions() == (board.rows, board.cols) => {
+                        checker
+                    }
+                    _ => checker
+                        .insert(ForwardChecker::new(board.rows, board.cols).with_fixed(&fixed)),
+                };
                 for row in 1..=board.rows {
                     for col in 1..=board.cols {
                         let new_piece = Piece {
                             row,
                             col,
                             piece: pieces[0],
+                            colour: None,
                         };
+                        sink.check();
                         if board.is_safe(new_piece) {
                             let new_board = board_rc.place(new_piece);
                             let new_board_rc = Rc::new(new_board);
                             if pieces.len() != 1 {
-                                if tested_configurations.insert(Rc::clone(&new_board_rc)) {
-                                    let tail = &pieces[1..pieces.len()];
+                                let tail = &pieces[1..pieces.len()];
+                                if !tested_configurations.insert(Rc::clone(&new_board_rc)) {
+                                    sink.duplicate();
+                                    continue;
+                                }
+                                sink.store(&new_board_rc);
+                                if !checker.fits(&new_board_rc, tail) {
+                                    sink.prune();
+                                } else {
                                     board_stack.push_front((Rc::clone(&new_board_rc), tail));
+                                    sink.push(board_stack.len());
                                 }
-                            } else {
-                                solutions.insert(new_board_rc);
+                            } else if solutions.insert(Rc::clone(&new_board_rc)) {
+                                sink.store(&new_board_rc);
                             }
                         }
                     }
                 }
+                sink.expanded(&board_rc, board_stack, solutions, tested_configurations);
             },
         }
     }
@@ -1,8 +1,134 @@
-use chess::{solution, Board, ChessPiece};
-use std::{collections::HashSet, collections::VecDeque};
+use chess::backend::Backend;
+use chess::bigint::BigUint;
+use chess::binary::{self, Header};
+use chess::checkpoint::Checkpoint;
+use chess::heatmap::Heatmap;
+use chess::notation::{format_pieces, parse_pieces, read_solutions};
+use chess::order::PieceOrder;
+use chess::packed::PackedBoard;
+use chess::rules::{AttackMatrix, Rules};
+use chess::sample::Sampler;
+use chess::store::{Query, Store};
+use chess::sweep::Sweep;
+use chess::{
+    armies, dimacs, estimate, oeis, search, shard, solution, solution_with_stats, transfer, Board,
+    ChessPiece, MAX_SIZE,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process;
 use std::rc::Rc;
 
+const USAGE: &str = "usage:
+    chess
+    chess solve [PROBLEM] [--order ORDER] [--seed S] [--stats]
+    chess count [PROBLEM] [RULES] [--order ORDER] [--seed S] [--backend backtracking|dlx]
+                [--checkpoint FILE] [--every N]
+    chess orders [PROBLEM] [--seed S]
+    chess resume --checkpoint FILE [--every N]
+    chess shard --index I --of K [PROBLEM] [--solutions] [--output FILE] [--checkpoint FILE]
+    chess merge [--output FILE] FILE...
+    chess sample [PROBLEM] [--samples N] [--seed S]
+    chess estimate [PROBLEM] [--probes N] [--seed S]
+    chess verify [PROBLEM] FILE...
+    chess dump [PROBLEM] [--binary] [--output FILE]
+    chess read FILE... [--count]
+    chess query FILE... [--where QUERY] [--count]
+    chess heatmap [PROBLEM] [--svg FILE]
+    chess sweep --pieces K [--rows A..B] [--cols A..B] [--square] [--counts A..B]
+                [--backend backtracking|dlx] [--markdown]
+    chess transfer --rows R --cols C --pieces KKNN
+    chess cnf [PROBLEM] [--output FILE]
+    chess decode [PROBLEM] FILE
+    chess armies --size N
+    chess oeis [--terms N]
+
+PROBLEM is [--rows R] [--cols C] [--pieces KKQQBBN], the 7x7 challenge by default.
+RULES are [--max-attackers K] [--no-pawns-on-back-ranks] [--allow-attacks K*,QN],
+no attacks at all by default.
+ORDER is given, most-constraining or random, the order in which pieces are placed.
+QUERY is a list of filters such as 'Q(4,4) all:K(edge) no:N(1,_) _(2..3,1..2)'.";
+
+/// Options followed by a value.
+const OPTIONS: &[&str] = &[
+    "allow-attacks",
+    "backend",
+    "checkpoint",
+    "cols",
+    "counts",
+    "every",
+    "index",
+    "max-attackers",
+    "of",
+    "order",
+    "output",
+    "pieces",
+    "probes",
+    "rows",
+    "samples",
+    "seed",
+    "size",
+    "svg",
+    "terms",
+    "where",
+];
+
+/// Options that take no value, so the argument after them is never consumed.
+const FLAGS: &[&str] = &[
+    "binary",
+    "count",
+    "markdown",
+    "no-pawns-on-back-ranks",
+    "solutions",
+    "square",
+    "stats",
+];
+
+/// Number of frontier entries expanded between two checkpoint saves.
+const DEFAULT_CHECKPOINT_INTERVAL: u64 = 100_000;
+
+/// Number of random paths used by `chess estimate`.
+const DEFAULT_PROBES: u64 = 10_000;
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        None => {
+            challenge();
+            Ok(())
+        }
+        Some("solve") => Args::parse(&args[1..]).and_then(|args| solve(&args)),
+        Some("count") => Args::parse(&args[1..]).and_then(|args| count(&args)),
+        Some("orders") => Args::parse(&args[1..]).and_then(|args| orders(&args)),
+        Some("resume") => Args::parse(&args[1..]).and_then(|args| resume(&args)),
+        Some("shard") => Args::parse(&args[1..]).and_then(|args| run_shard(&args)),
+        Some("merge") => Args::parse(&args[1..]).and_then(|args| merge(&args)),
+        Some("sample") => Args::parse(&args[1..]).and_then(|args| sample(&args)),
+        Some("estimate") => Args::parse(&args[1..]).and_then(|args| run_estimate(&args)),
+        Some("verify") => Args::parse(&args[1..]).and_then(|args| verify(&args)),
+        Some("dump") => Args::parse(&args[1..]).and_then(|args| dump(&args)),
+        Some("read") => Args::parse(&args[1..]).and_then(|args| read(&args)),
+        Some("query") => Args::parse(&args[1..]).and_then(|args| query(&args)),
+        Some("heatmap") => Args::parse(&args[1..]).and_then(|args| heatmap(&args)),
+        Some("sweep") => Args::parse(&args[1..]).and_then(|args| sweep(&args)),
+        Some("transfer") => Args::parse(&args[1..]).and_then(|args| run_transfer(&args)),
+        Some("cnf") => Args::parse(&args[1..]).and_then(|args| cnf(&args)),
+        Some("decode") => Args::parse(&args[1..]).and_then(|args| decode(&args)),
+        Some("oeis") => Args::parse(&args[1..]).and_then(|args| run_oeis(&args)),
+        Some("armies") => Args::parse(&args[1..]).and_then(|args| run_armies(&args)),
+        Some(command) => Err(format!("unknown command `{}`\n{}", command, USAGE)),
+    };
+    if let Err(message) = result {
+        eprintln!("chess: {}", message);
+        process::exit(1);
+    }
+}
+
+/// Solves the original challenge: 2 kings, 2 queens, 2 bishops and a knight on a 7x7 board.
+fn challenge() {
     let board = Board {
         rows: 7,
         cols: 7,
@@ -23,6 +149,550 @@ fn main() {
     board_stack.push_front((board_rc, &pieces));
     solution(&mut board_stack, &mut solutions, &mut HashSet::new());
     println!("Size {}", solutions.len()); // it has to be 3063828
+}
+
+/// Solves a problem with [`solution`], printing statistics on the search with `--stats`.
+fn solve(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let order: PieceOrder = args.number("order", PieceOrder::default())?;
+    let pieces = order.arrange(&board, &pieces, args.number("seed", 0)?);
+    let mut solutions: HashSet<Rc<Board>> = HashSet::new();
+    let mut board_stack: VecDeque<(Rc<Board>, &[ChessPiece])> = VecDeque::new();
+    board_stack.push_front((Rc::new(board), &pieces));
+    let mut tested_configurations = HashSet::new();
+    if args.flag("stats") {
+        let (solutions, stats) =
+            solution_with_stats(&mut board_stack, &mut solutions, &mut tested_configurations);
+        println!("Size {}", solutions.len());
+        println!("{}", stats);
+    } else {
+        let solutions = solution(&mut board_stack, &mut solutions, &mut tested_configurations);
+        println!("Size {}", solutions.len());
+    }
+    Ok(())
+}
+
+fn count(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let order: PieceOrder = args.number("order", PieceOrder::default())?;
+    let pieces = order.arrange(&board, &pieces, args.number("seed", 0)?);
+    let rules = args.rules()?;
+    let backend: Backend = args.number("backend", Backend::default())?;
+    let count = match backend {
+        _ if rules != Rules::default() => {
+            if backend != Backend::Backtracking || args.flag("checkpoint") {
+                return Err(
+                    "rules other than the default need the backtracking backend without \
+                     --checkpoint"
+                        .to_string(),
+                );
+            }
+            search::count_with(&board, &pieces, &rules)
+        }
+        Backend::Backtracking => run_checkpoint(Checkpoint::new(board, &pieces), args)?.solutions,
+        _ if args.flag("checkpoint") => {
+            return Err(format!("the {} backend can't be checkpointed", backend));
+        }
+        _ => backend.count(&board, &pieces),
+    };
+    println!("Size {}", count);
+    Ok(())
+}
+
+/// Compares the piece orders by the number of boards the search goes through.
+fn orders(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let seed = args.number("seed", 0)?;
+    for order in PieceOrder::ALL {
+        let pieces = order.arrange(&board, &pieces, seed);
+        println!(
+            "{:<18} {:<12} {} nodes",
+            order.to_string(),
+            format_pieces(&pieces),
+            search::nodes(&board, &pieces)
+        );
+    }
+    Ok(())
+}
+
+fn resume(args: &Args) -> Result<(), String> {
+    let path = args
+        .path("checkpoint")
+        .ok_or("resume needs --checkpoint FILE")?;
+    let checkpoint =
+        Checkpoint::load(&path).map_err(|e| format!("cannot load {}: {}", path.display(), e))?;
+    let checkpoint = run_checkpoint(checkpoint, args)?;
+    println!("Size {}", checkpoint.solutions);
+    Ok(())
+}
+
+/// Runs a search to the end, saving it every `--every` expansions when `--checkpoint`
+/// is given.
+fn run_checkpoint(mut checkpoint: Checkpoint, args: &Args) -> Result<Checkpoint, String> {
+    match args.path("checkpoint") {
+        None => {
+            checkpoint.run(u64::MAX);
+        }
+        Some(path) => {
+            let every = args.number("every", DEFAULT_CHECKPOINT_INTERVAL)?;
+            while !checkpoint.run(every) {
+                checkpoint
+                    .save(&path)
+                    .map_err(|e| format!("cannot save {}: {}", path.display(), e))?;
+            }
+            checkpoint
+                .save(&path)
+                .map_err(|e| format!("cannot save {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(checkpoint)
+}
+
+/// Solves one shard of a problem, writing its count (and its solutions with
+/// `--solutions`) to `--output`.
+fn run_shard(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let of: usize = args.number("of", 0)?;
+    let index: usize = args.number("index", of)?;
+    if index >= of {
+        return Err("shard needs --index I --of K with 0 <= I < K".to_string());
+    }
+    if args.flag("solutions") && args.flag("checkpoint") {
+        return Err("shard can't save a --checkpoint while listing --solutions".to_string());
+    }
+    let checkpoint = shard::shard(&board, &pieces, index, of);
+    let mut boards = Vec::new();
+    let count = if args.flag("solutions") {
+        checkpoint.for_each_solution(|solution| {
+            let mut solution = solution.clone();
+            solution.pieces.sort();
+            boards.push(solution);
+        });
+        BigUint::from(boards.len() as u64)
+    } else {
+        run_checkpoint(checkpoint, args)?.solutions
+    };
+    if let Some(path) = args.path("output") {
+        File::create(&path)
+            .and_then(|file| shard::write_result(file, &count, &boards))
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+    println!("Size {}", count);
+    Ok(())
+}
+
+/// Adds up the results written by `chess shard`.
+fn merge(args: &Args) -> Result<(), String> {
+    let results = args
+        .files
+        .iter()
+        .map(|path| {
+            File::open(path)
+                .map(BufReader::new)
+                .map_err(|e| format!("cannot open {}: {}", path, e))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let (count, boards) = shard::merge(results).map_err(|e| e.to_string())?;
+    if let Some(path) = args.path("output") {
+        File::create(&path)
+            .and_then(|file| shard::write_result(file, &count, &boards))
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+    println!("Size {}", count);
+    Ok(())
+}
+
+/// Prints `--samples` solutions drawn uniformly at random.
+fn sample(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let samples = args.number("samples", 1)?;
+    let mut sampler = Sampler::new(board, &pieces, args.number("seed", 0)?);
+    for _ in 0..samples {
+        match sampler.sample() {
+            Some(board) => println!("{}", board),
+            None => return Err("the problem has no solution".to_string()),
+        }
+    }
+    Ok(())
+}
+
+/// Estimates the number of solutions from `--probes` random paths of the search tree.
+fn run_estimate(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let probes = args.number("probes", DEFAULT_PROBES)?;
+    let estimate = estimate::estimate(&board, &pieces, probes, args.number("seed", 0)?);
+    println!("Estimate {:.0}", estimate.count);
+    println!(
+        "95% confidence interval [{:.0}, {:.0}] from {} probes",
+        estimate.lower, estimate.upper, estimate.probes
+    );
+    Ok(())
+}
+
+/// Checks every board of the given solution files against the problem, reporting the
+/// invalid ones and the boards found more than once.
+fn verify(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let mut seen = HashSet::new();
+    let mut checked = 0;
+    let mut invalid = 0;
+    let mut duplicates = 0;
+    for path in &args.files {
+        let boards = File::open(path)
+            .and_then(|file| read_solutions(BufReader::new(file)))
+            .map_err(|e| format!("cannot read {}: {}", path, e))?;
+        for (index, solution) in boards.into_iter().enumerate() {
+            checked += 1;
+            let result = if (solution.rows, solution.cols) != (board.rows, board.cols) {
+                Err(format!(
+                    "expected a {}x{} board but found {}x{}",
+                    board.rows, board.cols, solution.rows, solution.cols
+                ))
+            } else {
+                solution.validate(&pieces).map_err(|e| e.to_string())
+            };
+            if let Err(violation) = result {
+                println!("{}: board {}: {}", path, index + 1, violation);
+                invalid += 1;
+            } else if !seen.insert(solution) {
+                println!("{}: board {}: duplicate solution", path, index + 1);
+                duplicates += 1;
+            }
+        }
+    }
+    println!(
+        "{} boards checked, {} invalid, {} duplicates",
+        checked, invalid, duplicates
+    );
+    if invalid + duplicates > 0 {
+        return Err("verification failed".to_string());
+    }
+    Ok(())
+}
+
+/// Writes every solution of the problem to `--output`, or to standard output, as text
+/// or in the binary format of [`binary`] with `--binary`.
+///
+/// Text solutions are written as they are found. The binary header starts with the
+/// number of solutions, so those are packed as they are found and written at the end.
+fn dump(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let write = |writer: &mut dyn Write| {
+        // The first error stops the writing, though not the search.
+        let mut result = Ok(());
+        if args.flag("binary") {
+            // The header comes first, so the solutions are counted before being written.
+            let count = search::count(&board, &pieces).to_u64().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "too many solutions to write")
+            })?;
+            let header = Header::new(board.rows, board.cols, &pieces, count);
+            let mut writer = binary::Writer::new(writer, &header)?;
+            search::for_each_solution(&board, &pieces, |solution| {
+                if result.is_ok() {
+                    result = PackedBoard::try_from(solution)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                        .and_then(|solution| writer.write(&solution));
+                }
+            });
+            result?;
+            writer.finish()
+        } else {
+            let mut writer = BufWriter::new(writer);
+            search::for_each_solution(&board, &pieces, |solution| {
+                if result.is_ok() {
+                    let mut solution = solution.clone();
+                    solution.pieces.sort();
+                    result = writeln!(writer, "{}", solution);
+                }
+            });
+            result?;
+            writer.flush()
+        }
+    };
+    match args.path("output") {
+        Some(path) => File::create(&path)
+            .and_then(|mut file| write(&mut file))
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e)),
+        None => write(&mut io::stdout().lock()).map_err(|e| e.to_string()),
+    }
+}
+
+/// Prints the solutions stored in binary solution files, or only how many there are
+/// with `--count`.
+fn read(args: &Args) -> Result<(), String> {
+    for path in &args.files {
+        let reader = File::open(path)
+            .map(BufReader::new)
+            .and_then(binary::Reader::new)
+            .map_err(|e| format!("cannot read {}: {}", path, e))?;
+        let header = reader.header.clone();
+        if args.flag("count") {
+            println!(
+                "{}: {}x{} {} Size {}",
+                path,
+                header.rows,
+                header.cols,
+                format_pieces(&header.pieces),
+                header.count
+            );
+            continue;
+        }
+        for board in reader {
+            let board = board.map_err(|e| format!("cannot read {}: {}", path, e))?;
+            println!("{}", board.unpack());
+        }
+    }
+    Ok(())
+}
+
+/// Prints the boards of text or binary solution files that match `--where`, or only how
+/// many there are with `--count`.
+fn query(args: &Args) -> Result<(), String> {
+    let query = args
+        .get("where")
+        .unwrap_or("")
+        .parse::<Query>()
+        .map_err(|e| e.to_string())?;
+    let mut store = Store::new();
+    for path in &args.files {
+        File::open(path)
+            .and_then(|file| store.load(BufReader::new(file)))
+            .map_err(|e| format!("cannot read {}: {}", path, e))?;
+    }
+    if args.flag("count") {
+        println!("Size {}", store.count(&query));
+    } else {
+        for board in store.select(&query) {
+            println!("{}", board);
+        }
+    }
+    Ok(())
+}
+
+/// Prints how often each piece type stands on each square over all the solutions, and
+/// writes the same as an SVG image to `--svg`.
+fn heatmap(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let heatmap = Heatmap::of(&board, &pieces);
+    println!("{}", heatmap);
+    if let Some(path) = args.path("svg") {
+        fs::write(&path, heatmap.to_svg())
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Prints a table of solution counts for every board size of `--rows`×`--cols` and
+/// every number of copies of `--pieces` in `--counts`, as CSV or with `--markdown`.
+fn sweep(args: &Args) -> Result<(), String> {
+    let rows = args.range("rows", 1..=8)?;
+    let cols = args.range("cols", rows.clone())?;
+    if *rows.start() < 1 || *cols.start() < 1 {
+        return Err("board sizes start at 1".to_string());
+    }
+    if *rows.end() > MAX_SIZE || *cols.end() > MAX_SIZE {
+        return Err(format!("board sizes go up to {}", MAX_SIZE));
+    }
+    let pattern = parse_pieces(args.get("pieces").ok_or("sweep needs --pieces")?)
+        .map_err(|e| e.to_string())?;
+    let mut sweep = Sweep::new(rows, cols, pattern, args.range("counts", 0..=8)?);
+    sweep.square_only = args.flag("square");
+    sweep.backend = args.number("backend", Backend::default())?;
+    let table = sweep.run();
+    if args.flag("markdown") {
+        print!("{}", table.to_markdown());
+    } else {
+        print!("{}", table.to_csv());
+    }
+    Ok(())
+}
+
+/// Counts the solutions of a king and knight problem row by row, which handles boards
+/// with thousands of rows as long as one side is short.
+fn run_transfer(args: &Args) -> Result<(), String> {
+    let rows: usize = args.number("rows", 7)?;
+    let cols: usize = args.number("cols", 7)?;
+    let pieces = parse_pieces(args.get("pieces").unwrap_or("")).map_err(|e| e.to_string())?;
+    let (long, short) = if rows >= cols {
+        (rows, cols)
+    } else {
+        (cols, rows)
+    };
+    if short > transfer::MAX_COLS {
+        return Err(format!(
+            "{}x{} is too wide, one side must be at most {}",
+            rows,
+            cols,
+            transfer::MAX_COLS
+        ));
+    }
+    let count = transfer::count(long, short, &pieces).map_err(|e| e.to_string())?;
+    println!("Size {}", count);
+    Ok(())
+}
+
+/// Writes the problem in DIMACS CNF format to `--output`, or to standard output.
+fn cnf(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let cnf = dimacs::encode(board.rows, board.cols, &pieces);
+    match args.path("output") {
+        Some(path) => File::create(&path)
+            .and_then(|file| cnf.write(file))
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e)),
+        None => cnf.write(io::stdout()).map_err(|e| e.to_string()),
+    }
+}
+
+/// Prints the boards described by SAT solver outputs for the problem's CNF.
+fn decode(args: &Args) -> Result<(), String> {
+    let (board, pieces) = args.problem()?;
+    let cnf = dimacs::encode(board.rows, board.cols, &pieces);
+    for path in &args.files {
+        let output =
+            fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+        println!("{}", cnf.decode(&dimacs::parse_model(&output)));
+    }
+    Ok(())
+}
+
+/// Finds the largest peaceable white and black queen armies on a `--size`×`--size`
+/// board and prints one arrangement.
+fn run_armies(args: &Args) -> Result<(), String> {
+    let size: i8 = args.number("size", 8)?;
+    if !(1..=armies::MAX_SIZE).contains(&size) {
+        return Err(format!(
+            "--size must be between 1 and {}, not {}",
+            armies::MAX_SIZE,
+            size
+        ));
+    }
+    let (armies, board) = armies::peaceable_queens(size);
+    println!("Armies {}", armies);
+    println!("{}", board);
+    Ok(())
+}
+
+/// Checks the first `--terms` terms of every reference sequence against the solver.
+fn run_oeis(args: &Args) -> Result<(), String> {
+    let terms = args.number("terms", usize::MAX)?;
+    let mut failed = 0;
+    for sequence in oeis::SEQUENCES {
+        match sequence.check(terms) {
+            Ok(()) => println!("{} ok     {}", sequence.id, sequence.name),
+            Err(mismatch) => {
+                println!("{} FAILED {}", sequence.id, mismatch);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{} sequences not reproduced", failed));
+    }
+    Ok(())
+}
+
+/// Command line options of the form `--name value` listed in [`OPTIONS`], flags of the
+/// form `--name` listed in [`FLAGS`], and file names.
+struct Args {
+    options: HashMap<String, String>,
+    files: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Args, String> {
+        let mut options = HashMap::new();
+        let mut files = Vec::new();
+        let mut args = args.iter().peekable();
+        while let Some(arg) = args.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                files.push(arg.clone());
+                continue;
+            };
+            let value = if FLAGS.contains(&name) {
+                String::new()
+            } else if OPTIONS.contains(&name) {
+                args.next_if(|value| !value.starts_with("--"))
+                    .cloned()
+                    .ok_or_else(|| format!("missing value for --{}", name))?
+            } else {
+                return Err(format!("unknown option `{}`\n{}", arg, USAGE));
+            };
+            options.insert(name.to_string(), value);
+        }
+        Ok(Args { options, files })
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn path(&self, name: &str) -> Option<PathBuf> {
+        self.get(name).map(PathBuf::from)
+    }
+
+    fn number<T: std::str::FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.get(name) {
+            None => Ok(default),
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("invalid value `{}` for --{}", value, name)),
+        }
+    }
+
+    /// Parses an inclusive range written `A..B` with `A <= B`, or a single value `A`.
+    fn range<T>(&self, name: &str, default: RangeInclusive<T>) -> Result<RangeInclusive<T>, String>
+    where
+        T: std::str::FromStr + PartialOrd + Copy,
+    {
+        let Some(value) = self.get(name) else {
+            return Ok(default);
+        };
+        let invalid = || format!("invalid range `{}` for --{}", value, name);
+        let (start, end) = value.split_once("..").unwrap_or((value, value));
+        let start = start.parse().map_err(|_| invalid())?;
+        let end = end.parse().map_err(|_| invalid())?;
+        if start > end {
+            return Err(invalid());
+        }
+        Ok(start..=end)
+    }
 
+    /// The empty board and the pieces described by `--rows`, `--cols` and `--pieces`,
+    /// defaulting to the original 7x7 challenge.
+    fn problem(&self) -> Result<(Board, Vec<ChessPiece>), String> {
+        let rows = self.number("rows", 7)?;
+        let cols = self.number("cols", 7)?;
+        if !(1..=MAX_SIZE).contains(&rows) || !(1..=MAX_SIZE).contains(&cols) {
+            return Err(format!(
+                "invalid board size {}x{}, sides go from 1 to {}",
+                rows, cols, MAX_SIZE
+            ));
+        }
+        let pieces =
+            parse_pieces(self.get("pieces").unwrap_or("KKQQBBN")).map_err(|e| e.to_string())?;
+        if pieces.len() > rows as usize * cols as usize {
+            return Err(format!(
+                "{} pieces don't fit on a {}x{} board",
+                pieces.len(),
+                rows,
+                cols
+            ));
+        }
+        Ok((Board::new(rows, cols, Vec::new()), pieces))
+    }
 
+    /// Returns the rules given by `--max-attackers`, `--no-pawns-on-back-ranks` and
+    /// `--allow-attacks`.
+    fn rules(&self) -> Result<Rules, String> {
+        Ok(Rules {
+            max_attackers: self.number("max-attackers", 0)?,
+            no_pawns_on_back_ranks: self.flag("no-pawns-on-back-ranks"),
+            attacks: self.number("allow-attacks", AttackMatrix::default())?,
+            ..Rules::default()
+        })
+    }
 }
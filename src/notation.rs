@@ -0,0 +1,257 @@
+//! Text notation for pieces, boards and solution files.
+//!
+//! Pieces are written with their usual letters (`K`, `Q`, `B`, `R`, `N`, `P`) and a placed
+//! piece as its letter followed by its 1-indexed coordinates, e.g. `Q(2,5)`. Pieces of
+//! a colour are prefixed with `w` or `b`, e.g. `wQ(2,5)`. A board is written on a single
+//! line as its dimensions followed by its pieces:
+//!
+//! ```text
+//! 7x7 K(1,1) K(1,3) Q(2,5)
+//! ```
+//!
+//! A solution file holds one board per line. Blank lines and lines starting with `#`
+//! are ignored.
+
+use crate::{Board, ChessPiece, Colour, Piece, MAX_SIZE};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// Error returned when a piece, board or piece list cannot be parsed.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseError {
+    message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(error: ParseError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+impl ChessPiece {
+    /// Returns the letter used to write this piece type.
+    pub fn symbol(self) -> char {
+        match self {
+            ChessPiece::Rook => 'R',
+            ChessPiece::Bishop => 'B',
+            ChessPiece::Knight => 'N',
+            ChessPiece::Queen => 'Q',
+            ChessPiece::King => 'K',
+            ChessPiece::Pawn => 'P',
+        }
+    }
+
+    /// Returns the piece type written with `symbol`, if any.
+    pub fn from_symbol(symbol: char) -> Option<ChessPiece> {
+        match symbol {
+            'R' => Some(ChessPiece::Rook),
+            'B' => Some(ChessPiece::Bishop),
+            'N' => Some(ChessPiece::Knight),
+            'Q' => Some(ChessPiece::Queen),
+            'K' => Some(ChessPiece::King),
+            'P' => Some(ChessPiece::Pawn),
+            _ => None,
+        }
+    }
+}
+
+impl Colour {
+    /// Returns the letter prefixed to pieces of this colour.
+    pub fn symbol(self) -> char {
+        match self {
+            Colour::White => 'w',
+            Colour::Black => 'b',
+        }
+    }
+
+    /// Returns the colour written with `symbol`, if any.
+    pub fn from_symbol(symbol: char) -> Option<Colour> {
+        match symbol {
+            'w' => Some(Colour::White),
+            'b' => Some(Colour::Black),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ChessPiece {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl fmt::Display for Piece {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(colour) = self.colour {
+            write!(f, "{}", colour.symbol())?;
+        }
+        write!(f, "{}({},{})", self.piece, self.row, self.col)
+    }
+}
+
+impl FromStr for Piece {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Piece, ParseError> {
+        let invalid = || ParseError::new(format!("invalid piece `{}`", s));
+        let mut chars = s.chars();
+        let colour = chars.clone().next().and_then(Colour::from_symbol);
+        if colour.is_some() {
+            chars.next();
+        }
+        let piece = chars
+            .next()
+            .and_then(ChessPiece::from_symbol)
+            .ok_or_else(invalid)?;
+        let coordinates = chars
+            .as_str()
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+        let (row, col) = coordinates.split_once(',').ok_or_else(invalid)?;
+        Ok(Piece {
+            row: row.trim().parse().map_err(|_| invalid())?,
+            col: col.trim().parse().map_err(|_| invalid())?,
+            piece,
+            colour,
+        })
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.rows, self.cols)?;
+        for piece in &self.pieces {
+            write!(f, " {}", piece)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Board {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Board, ParseError> {
+        let mut words = s.split_whitespace();
+        let dimensions = words
+            .next()
+            .ok_or_else(|| ParseError::new("empty board"))?;
+        let (rows, cols) = parse_dimensions(dimensions)?;
+        let mut pieces = words.map(str::parse).collect::<Result<Vec<Piece>, _>>()?;
+        pieces.sort();
+        Ok(Board::new(rows, cols, pieces))
+    }
+}
+
+/// The most pieces [`parse_pieces`] accepts, far more than fit on the largest board.
+pub const MAX_PIECES: usize = u16::MAX as usize;
+
+/// Parses board dimensions written as `ROWSxCOLS`, e.g. `7x7`, with both between 1 and
+/// [`MAX_SIZE`].
+pub fn parse_dimensions(s: &str) -> Result<(i8, i8), ParseError> {
+    let invalid = || ParseError::new(format!("invalid board dimensions `{}`", s));
+    let (rows, cols) = s.split_once('x').ok_or_else(invalid)?;
+    let rows = rows.parse().map_err(|_| invalid())?;
+    let cols = cols.parse().map_err(|_| invalid())?;
+    if !(1..=MAX_SIZE).contains(&rows) || !(1..=MAX_SIZE).contains(&cols) {
+        return Err(invalid());
+    }
+    Ok((rows, cols))
+}
+
+/// Parses a list of pieces such as `KKQQBBN`.
+///
+/// A letter may be preceded by a count, so `2K2Q2B1N` describes the same pieces as
+/// `KKQQBBN`. The pieces are returned in the order they are written, and there may be
+/// at most [`MAX_PIECES`] of them.
+///
+/// # Example
+///
+/// ```
+/// use chess::notation::parse_pieces;
+/// use chess::ChessPiece;
+///
+/// let pieces = parse_pieces("2KQ").unwrap();
+/// assert_eq!(pieces, vec![ChessPiece::King, ChessPiece::King, ChessPiece::Queen]);
+/// ```
+pub fn parse_pieces(s: &str) -> Result<Vec<ChessPiece>, ParseError> {
+    let too_many = || {
+        ParseError::new(format!(
+            "too many pieces in `{}`, at most {} are allowed",
+            s, MAX_PIECES
+        ))
+    };
+    let mut pieces = Vec::new();
+    let mut count: Option<usize> = None;
+    for symbol in s.chars() {
+        if let Some(digit) = symbol.to_digit(10) {
+            let more = count.unwrap_or(0).checked_mul(10);
+            count = Some(
+                more.and_then(|count| count.checked_add(digit as usize))
+                    .filter(|&count| count <= MAX_PIECES)
+                    .ok_or_else(too_many)?,
+            );
+            continue;
+        }
+        let piece = ChessPiece::from_symbol(symbol)
+            .ok_or_else(|| ParseError::new(format!("unknown piece `{}` in `{}`", symbol, s)))?;
+        let count = count.take().unwrap_or(1);
+        if pieces.len() + count > MAX_PIECES {
+            return Err(too_many());
+        }
+        pieces.extend(std::iter::repeat_n(piece, count));
+    }
+    if count.is_some() {
+        return Err(ParseError::new(format!("missing piece after count in `{}`", s)));
+    }
+    Ok(pieces)
+}
+
+/// Writes a list of pieces as the letters accepted by [`parse_pieces`].
+pub fn format_pieces(pieces: &[ChessPiece]) -> String {
+    pieces.iter().map(|piece| piece.symbol()).collect()
+}
+
+/// Writes boards to a solution file, one per line.
+pub fn write_solutions<'a, W, I>(mut writer: W, boards: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a Board>,
+{
+    for board in boards {
+        writeln!(writer, "{}", board)?;
+    }
+    writer.flush()
+}
+
+/// Reads all the boards of a solution file.
+pub fn read_solutions<R: BufRead>(reader: R) -> io::Result<Vec<Board>> {
+    let mut boards = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        boards.push(line.parse()?);
+    }
+    Ok(boards)
+}
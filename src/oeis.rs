@@ -0,0 +1,190 @@
+//! Known integer sequences the solver reproduces.
+//!
+//! Each [`Sequence`] holds the first terms of an entry of the On-Line Encyclopedia of
+//! Integer Sequences (<https://oeis.org>) along with the problem whose solution count
+//! gives term `n`. A mistake in the attack rules, such as a wrong knight move, changes
+//! some of these counts, so checking them is a cheap regression test of the solver.
+
+use crate::bigint::BigUint;
+use crate::{search, Board, ChessPiece};
+use std::error::Error;
+use std::fmt;
+
+/// A reference sequence and the problems that produce it.
+///
+/// # Example
+///
+/// ```
+/// use chess::oeis;
+///
+/// let queens = oeis::find("A000170").unwrap();
+/// assert_eq!(queens.term(8), Some(92));
+/// assert!(queens.check(6).is_ok());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Sequence {
+    /// The A-number of the sequence.
+    pub id: &'static str,
+    /// What the sequence counts.
+    pub name: &'static str,
+    /// The `n` of the first term.
+    pub first: usize,
+    /// The first terms, from OEIS.
+    pub terms: &'static [u64],
+    /// Computes term `n` with the solver.
+    count: fn(usize) -> BigUint,
+}
+
+/// A term the solver doesn't reproduce.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Mismatch {
+    pub id: &'static str,
+    pub n: usize,
+    pub expected: u64,
+    pub found: BigUint,
+}
+
+/// The reference sequences.
+pub const SEQUENCES: &[Sequence] = &[
+    Sequence {
+        id: "A000170",
+        name: "n nonattacking queens on an n x n board",
+        first: 1,
+        terms: &[1, 0, 0, 2, 10, 4, 40, 92, 352, 724],
+        count: |n| square(n, &vec![ChessPiece::Queen; n]),
+    },
+    Sequence {
+        id: "A000142",
+        name: "n nonattacking rooks on an n x n board",
+        first: 1,
+        terms: &[1, 2, 6, 24, 120, 720, 5040, 40320],
+        count: |n| square(n, &vec![ChessPiece::Rook; n]),
+    },
+    Sequence {
+        id: "A002464",
+        name: "n nonattacking rooks on an n x n board, no two of them diagonally adjacent",
+        first: 1,
+        terms: &[1, 0, 0, 2, 14, 90, 646, 5242],
+        count: hertzsprung,
+    },
+    Sequence {
+        id: "A201862",
+        name: "n nonattacking kings on an n x n board",
+        first: 1,
+        terms: &[1, 0, 8, 79, 1974, 62266, 2484382, 119138166],
+        count: |n| square(n, &vec![ChessPiece::King; n]),
+    },
+    Sequence {
+        id: "A201540",
+        name: "n nonattacking knights on an n x n board",
+        first: 1,
+        terms: &[1, 6, 36, 412, 9386, 257318, 8891854, 379978716],
+        count: |n| square(n, &vec![ChessPiece::Knight; n]),
+    },
+    Sequence {
+        id: "A036464",
+        name: "2 nonattacking queens on an n x n board",
+        first: 1,
+        terms: &[0, 0, 8, 44, 140, 340, 700, 1288, 2184, 3480],
+        count: |n| square(n, &[ChessPiece::Queen; 2]),
+    },
+    Sequence {
+        id: "A061995",
+        name: "2 nonattacking kings on an n x n board",
+        first: 1,
+        terms: &[0, 0, 16, 78, 228, 520, 1020, 1806, 2968, 4608],
+        count: |n| square(n, &[ChessPiece::King; 2]),
+    },
+    Sequence {
+        id: "A172132",
+        name: "2 nonattacking knights on an n x n board",
+        first: 1,
+        terms: &[0, 6, 28, 96, 252, 550, 1056, 1848, 3016, 4662],
+        count: |n| square(n, &[ChessPiece::Knight; 2]),
+    },
+    Sequence {
+        id: "A172123",
+        name: "2 nonattacking bishops on an n x n board",
+        first: 1,
+        terms: &[0, 4, 26, 92, 240, 520, 994, 1736, 2832, 4380],
+        count: |n| square(n, &[ChessPiece::Bishop; 2]),
+    },
+    Sequence {
+        id: "A000079",
+        name: "2n - 2 nonattacking bishops on an n x n board (2^n)",
+        first: 2,
+        terms: &[4, 8, 16, 32, 64, 128, 256],
+        count: |n| square(n, &vec![ChessPiece::Bishop; 2 * n - 2]),
+    },
+];
+
+/// Returns the reference sequence with the A-number `id`.
+pub fn find(id: &str) -> Option<&'static Sequence> {
+    SEQUENCES.iter().find(|sequence| sequence.id == id)
+}
+
+impl Sequence {
+    /// Returns the known term `n`, if it is one of the embedded terms.
+    pub fn term(&self, n: usize) -> Option<u64> {
+        self.terms.get(n.checked_sub(self.first)?).copied()
+    }
+
+    /// Computes term `n` with the solver.
+    pub fn count(&self, n: usize) -> BigUint {
+        (self.count)(n)
+    }
+
+    /// Checks the first `terms` embedded terms against the solver.
+    pub fn check(&self, terms: usize) -> Result<(), Mismatch> {
+        for (n, &expected) in (self.first..).zip(self.terms).take(terms) {
+            let found = self.count(n);
+            if found != BigUint::from(expected) {
+                return Err(Mismatch {
+                    id: self.id,
+                    n,
+                    expected,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Counts the solutions of placing `pieces` on an empty `n`×`n` board.
+fn square(n: usize, pieces: &[ChessPiece]) -> BigUint {
+    search::count(&Board::new(n as i8, n as i8, Vec::new()), pieces)
+}
+
+/// Counts the placements of `n` nonattacking rooks with no two of them next to each
+/// other diagonally, Hertzsprung's problem.
+fn hertzsprung(n: usize) -> BigUint {
+    let mut total = 0u64;
+    let rooks = vec![ChessPiece::Rook; n];
+    search::for_each_solution(
+        &Board::new(n as i8, n as i8, Vec::new()),
+        &rooks,
+        |solution| {
+            let adjacent = solution.pieces.iter().any(|a| {
+                solution
+                    .pieces
+                    .iter()
+                    .any(|b| (a.row - b.row).abs() == 1 && (a.col - b.col).abs() == 1)
+            });
+            total += !adjacent as u64;
+        },
+    );
+    BigUint::from(total)
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({}): expected {} but found {}",
+            self.id, self.n, self.expected, self.found
+        )
+    }
+}
+
+impl Error for Mismatch {}
@@ -0,0 +1,104 @@
+//! A brute-force solver to check the others against.
+//!
+//! It tries every way of giving each piece type its own set of squares and keeps the
+//! boards where no piece attacks another, checking every pair with [`Piece::attacks`].
+//! Nothing is pruned and nothing is shared with the other solvers, which makes it slow
+//! but hard to get wrong: use it on small boards to test faster solvers.
+
+use crate::{Board, ChessPiece, Piece};
+use std::collections::HashSet;
+
+/// Finds every configuration obtained by placing `pieces` on the free squares of
+/// `board`, with the pieces of each board sorted.
+///
+/// # Example
+///
+/// ```
+/// use chess::{oracle, Board, ChessPiece};
+///
+/// let board = Board::new(3, 3, Vec::new());
+/// let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+/// assert_eq!(oracle::solutions(&board, &pieces).len(), 4);
+/// ```
+pub fn solutions(board: &Board, pieces: &[ChessPiece]) -> HashSet<Board> {
+    let mut groups: Vec<(ChessPiece, usize)> = Vec::new();
+    let mut sorted = pieces.to_vec();
+    sorted.sort();
+    for chess_piece in sorted {
+        match groups.last_mut() {
+            Some((last, count)) if *last == chess_piece => *count += 1,
+            _ => groups.push((chess_piece, 1)),
+        }
+    }
+    let mut solutions = HashSet::new();
+    let mut placed = board.pieces.clone();
+    place_groups(board, &groups, &mut placed, &mut solutions);
+    solutions
+}
+
+/// Counts the configurations obtained by placing `pieces` on the free squares of
+/// `board`.
+pub fn count(board: &Board, pieces: &[ChessPiece]) -> usize {
+    solutions(board, pieces).len()
+}
+
+/// Gives the first group every combination of squares still free, then the next group.
+fn place_groups(
+    board: &Board,
+    groups: &[(ChessPiece, usize)],
+    placed: &mut Vec<Piece>,
+    solutions: &mut HashSet<Board>,
+) {
+    let Some((&(chess_piece, count), rest)) = groups.split_first() else {
+        let peaceful = placed.iter().enumerate().all(|(i, &a)| {
+            placed[i + 1..]
+                .iter()
+                .all(|&b| !a.attacks(b) && !b.attacks(a))
+        });
+        if peaceful {
+            let mut pieces = placed.clone();
+            pieces.sort();
+            solutions.insert(Board::new(board.rows, board.cols, pieces));
+        }
+        return;
+    };
+    let squares: Vec<(i8, i8)> = (1..=board.rows)
+        .flat_map(|row| (1..=board.cols).map(move |col| (row, col)))
+        .filter(|&(row, col)| {
+            placed
+                .iter()
+                .all(|piece| (piece.row, piece.col) != (row, col))
+        })
+        .collect();
+    let mut chosen = Vec::with_capacity(count);
+    choose(&squares, count, &mut chosen, &mut |chosen| {
+        let before = placed.len();
+        placed.extend(chosen.iter().map(|&(row, col)| Piece {
+            row,
+            col,
+            piece: chess_piece,
+            colour: None,
+        }));
+        place_groups(board, rest, placed, solutions);
+        placed.truncate(before);
+    });
+}
+
+/// Calls `visit` with every combination of `count` of `squares`, in order.
+fn choose<F>(squares: &[(i8, i8)], count: usize, chosen: &mut Vec<(i8, i8)>, visit: &mut F)
+where
+    F: FnMut(&[(i8, i8)]),
+{
+    if count == 0 {
+        visit(chosen);
+        return;
+    }
+    for i in 0..squares.len() {
+        if squares.len() - i < count {
+            break;
+        }
+        chosen.push(squares[i]);
+        choose(&squares[i + 1..], count - 1, chosen, visit);
+        chosen.pop();
+    }
+}
@@ -0,0 +1,189 @@
+//! A compact encoding of boards, for storing many solutions.
+//!
+//! A [`Board`] keeps its pieces in a vector on the heap, and the solvers wrap it in an
+//! `Rc`, which comes to about 90 bytes for a seven-piece solution. A [`PackedBoard`]
+//! takes 24 bytes and no heap at all: each piece is a single byte holding the index of
+//! its square, and the type of each piece follows from how many pieces of each type the
+//! board holds, the squares of the rooks coming first, then those of the bishops, and so
+//! on in the order of [`ChessPiece`]. Boards of up to 256 squares with up to
+//! [`CAPACITY`] colourless pieces can be packed.
+
+use crate::{Board, ChessPiece, Piece};
+use std::error::Error;
+use std::fmt;
+
+/// The most pieces a packed board holds.
+pub const CAPACITY: usize = 16;
+
+/// A board and its pieces in 24 bytes.
+///
+/// Two boards with the same pieces pack the same way whatever the order of their
+/// pieces, so packed boards can be compared and hashed directly.
+///
+/// # Example
+///
+/// ```
+/// use chess::packed::PackedBoard;
+/// use chess::Board;
+///
+/// let board: Board = "7x7 K(1,1) N(3,5) Q(7,2)".parse().unwrap();
+/// let packed = PackedBoard::try_from(&board).unwrap();
+/// assert_eq!(packed.len(), 3);
+/// assert_eq!(packed.unpack(), board);
+/// assert_eq!(std::mem::size_of::<PackedBoard>(), 24);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct PackedBoard {
+    rows: u8,
+    cols: u8,
+    /// The number of pieces of each type.
+    counts: [u8; ChessPiece::ALL.len()],
+    /// The square indices, `(row - 1) * cols + col - 1`, grouped by type and increasing
+    /// within a type. Unused entries are zero.
+    squares: [u8; CAPACITY],
+}
+
+/// The reason a board can't be packed.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PackError {
+    /// The board has more than 256 squares.
+    TooLarge { rows: i8, cols: i8 },
+    /// The board holds more than [`CAPACITY`] pieces.
+    TooManyPieces(usize),
+    /// A piece lies outside the board.
+    OffBoard(Piece),
+    /// A piece belongs to an army, which isn't packed.
+    Coloured(Piece),
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::TooLarge { rows, cols } => {
+                write!(f, "a {}x{} board has too many squares to pack", rows, cols)
+            }
+            PackError::TooManyPieces(pieces) => write!(
+                f,
+                "{} pieces are too many to pack, at most {} are",
+                pieces, CAPACITY
+            ),
+            PackError::OffBoard(piece) => write!(f, "{} is off the board", piece),
+            PackError::Coloured(piece) => {
+                write!(f, "{} has a colour, which can't be packed", piece)
+            }
+        }
+    }
+}
+
+impl Error for PackError {}
+
+impl PackedBoard {
+    /// Returns the number of rows and columns of the board.
+    pub fn dimensions(&self) -> (i8, i8) {
+        (self.rows as i8, self.cols as i8)
+    }
+
+    /// Returns the number of pieces on the board.
+    pub fn len(&self) -> usize {
+        self.counts.iter().map(|&count| count as usize).sum()
+    }
+
+    /// Returns `true` if the board holds no pieces.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the pieces of the board, grouped by type.
+    pub fn pieces(&self) -> impl Iterator<Item = Piece> + '_ {
+        let cols = self.cols as usize;
+        ChessPiece::ALL
+            .iter()
+            .zip(self.counts)
+            .flat_map(|(&chess_piece, count)| std::iter::repeat_n(chess_piece, count as usize))
+            .zip(self.squares)
+            .map(move |(chess_piece, square)| Piece {
+                row: (square as usize / cols) as i8 + 1,
+                col: (square as usize % cols) as i8 + 1,
+                piece: chess_piece,
+                colour: None,
+            })
+    }
+
+    /// Returns the number of pieces of each type, in declaration order.
+    pub(crate) fn counts(&self) -> [u8; ChessPiece::ALL.len()] {
+        self.counts
+    }
+
+    /// Returns the square of each piece, grouped by type.
+    pub(crate) fn squares(&self) -> &[u8] {
+        &self.squares[..self.len()]
+    }
+
+    /// Builds a packed board from its parts, which must hold no more than [`CAPACITY`]
+    /// squares, grouped by type and increasing within a type.
+    pub(crate) fn from_parts(
+        rows: i8,
+        cols: i8,
+        counts: [u8; ChessPiece::ALL.len()],
+        squares: &[u8],
+    ) -> PackedBoard {
+        let mut packed = PackedBoard {
+            rows: rows as u8,
+            cols: cols as u8,
+            counts,
+            squares: [0; CAPACITY],
+        };
+        packed.squares[..squares.len()].copy_from_slice(squares);
+        packed
+    }
+
+    /// Returns the board, with its pieces sorted.
+    pub fn unpack(&self) -> Board {
+        let mut pieces: Vec<Piece> = self.pieces().collect();
+        pieces.sort();
+        Board::new(self.rows as i8, self.cols as i8, pieces)
+    }
+}
+
+impl TryFrom<&Board> for PackedBoard {
+    type Error = PackError;
+
+    fn try_from(board: &Board) -> Result<PackedBoard, PackError> {
+        let (rows, cols) = (board.rows, board.cols);
+        if rows < 1 || cols < 1 || rows as usize * cols as usize > 256 {
+            return Err(PackError::TooLarge { rows, cols });
+        }
+        if board.pieces.len() > CAPACITY {
+            return Err(PackError::TooManyPieces(board.pieces.len()));
+        }
+        let mut keyed = Vec::with_capacity(board.pieces.len());
+        for &piece in &board.pieces {
+            if piece.colour.is_some() {
+                return Err(PackError::Coloured(piece));
+            }
+            if !(1..=rows).contains(&piece.row) || !(1..=cols).contains(&piece.col) {
+                return Err(PackError::OffBoard(piece));
+            }
+            let square = (piece.row - 1) as usize * cols as usize + (piece.col - 1) as usize;
+            keyed.push((piece.piece, square as u8));
+        }
+        keyed.sort();
+        let mut packed = PackedBoard {
+            rows: rows as u8,
+            cols: cols as u8,
+            counts: [0; ChessPiece::ALL.len()],
+            squares: [0; CAPACITY],
+        };
+        for (i, (chess_piece, square)) in keyed.into_iter().enumerate() {
+            packed.counts[chess_piece as usize] += 1;
+            packed.squares[i] = square;
+        }
+        Ok(packed)
+    }
+}
+
+impl From<&PackedBoard> for Board {
+    fn from(packed: &PackedBoard) -> Board {
+        packed.unpack()
+    }
+}
@@ -0,0 +1,174 @@
+//! Forward checking.
+//!
+//! After a piece is placed, the squares still safe for every remaining piece type are
+//! counted. A branch is abandoned as soon as some type has fewer safe squares than
+//! pieces left to place, or fewer squares suit any remaining piece than there are
+//! pieces left: placing more pieces only removes safe squares, so the branch can't
+//! lead to a solution.
+//!
+//! The squares each piece type attacks from each square are computed once per board
+//! size as bit masks, which makes a check a handful of mask operations per piece on the
+//! board. Pieces of the same type are assumed to be placed in increasing square order,
+//! as in [`crate::search`], so only squares after the last one of a type count for it.
+//! The pieces already on the board when the search starts may stand anywhere, and are
+//! left out of that rule with [`ForwardChecker::with_fixed`].
+
+use crate::{Board, ChessPiece, Piece};
+
+/// Forward checking for boards of one size.
+///
+/// # Example
+///
+/// ```
+/// use chess::prune::ForwardChecker;
+/// use chess::{Board, ChessPiece};
+///
+/// let checker = ForwardChecker::new(3, 3);
+/// let board: Board = "3x3 Q(1,1)".parse().unwrap();
+/// // Only two squares are out of the queen's reach.
+/// assert!(checker.fits(&board, &[ChessPiece::Queen; 2]));
+/// assert!(!checker.fits(&board, &[ChessPiece::Queen; 3]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ForwardChecker {
+    rows: i8,
+    cols: i8,
+    /// `attacks[square][type]`: the squares a piece of the type attacks from the square,
+    /// its own included. Empty when the board has more than 128 squares.
+    attacks: Vec<[u128; ChessPiece::ALL.len()]>,
+    /// `attacked_from[square][type]`: the squares from which a piece of the type
+    /// attacks the square.
+    attacked_from: Vec<[u128; ChessPiece::ALL.len()]>,
+    /// The squares of the pieces that were on the board before the search started.
+    fixed: u128,
+}
+
+impl ForwardChecker {
+    /// Precomputes the attack masks of a `rows`×`cols` board.
+    ///
+    /// Boards of more than 128 squares are not checked: [`ForwardChecker::fits`] always
+    /// returns `true` for them.
+    pub fn new(rows: i8, cols: i8) -> ForwardChecker {
+        let mut checker = ForwardChecker {
+            rows,
+            cols,
+            attacks: Vec::new(),
+            attacked_from: Vec::new(),
+            fixed: 0,
+        };
+        let squares = rows as usize * cols as usize;
+        if squares > 128 {
+            return checker;
+        }
+        checker.attacks = vec![[0; ChessPiece::ALL.len()]; squares];
+        checker.attacked_from = vec![[0; ChessPiece::ALL.len()]; squares];
+        for (t, &chess_piece) in ChessPiece::ALL.iter().enumerate() {
+            for a in 0..squares {
+                let attacker = checker.piece(a, chess_piece);
+                for b in 0..squares {
+                    if attacker.attacks(checker.piece(b, chess_piece)) {
+                        checker.attacks[a][t] |= 1 << b;
+                        checker.attacked_from[b][t] |= 1 << a;
+                    }
+                }
+            }
+        }
+        checker
+    }
+
+    /// Marks `pieces` as standing on the board before the search started: the pieces
+    /// placed after them come in increasing square order, but these may stand anywhere.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::prune::ForwardChecker;
+    /// use chess::{Board, ChessPiece};
+    ///
+    /// let board: Board = "4x4 K(4,4)".parse().unwrap();
+    /// // Another king would come before the one on the board, unless it was there first.
+    /// assert!(!ForwardChecker::new(4, 4).fits(&board, &[ChessPiece::King]));
+    /// let checker = ForwardChecker::new(4, 4).with_fixed(&board.pieces);
+    /// assert!(checker.fits(&board, &[ChessPiece::King]));
+    /// ```
+    pub fn with_fixed(mut self, pieces: &[Piece]) -> ForwardChecker {
+        if self.attacks.is_empty() {
+            return self;
+        }
+        for piece in pieces {
+            if (1..=self.rows).contains(&piece.row) && (1..=self.cols).contains(&piece.col) {
+                self.fixed |= 1 << self.square(piece);
+            }
+        }
+        self
+    }
+
+    /// Returns the dimensions of the boards this checker is for.
+    pub fn dimensions(&self) -> (i8, i8) {
+        (self.rows, self.cols)
+    }
+
+    /// Returns `false` if `pieces` certainly can't all be added to `board`; `true` means
+    /// they might.
+    ///
+    /// Boards of another size than the checker's, and boards holding pieces of a colour,
+    /// are not checked.
+    pub fn fits(&self, board: &Board, pieces: &[ChessPiece]) -> bool {
+        if pieces.is_empty()
+            || self.attacks.is_empty()
+            || (board.rows, board.cols) != (self.rows, self.cols)
+            || board.pieces.iter().any(|piece| piece.colour.is_some())
+        {
+            return true;
+        }
+        let mut occupied = 0u128;
+        let mut attacked = 0u128;
+        // Squares where a piece of each type would attack a piece on the board.
+        let mut threatening = [0u128; ChessPiece::ALL.len()];
+        // Squares after the last piece of each type.
+        let squares = self.attacks.len() as u32;
+        let mut after = [u128::MAX >> (128 - squares); ChessPiece::ALL.len()];
+        for piece in &board.pieces {
+            let square = self.square(piece);
+            occupied |= 1 << square;
+            attacked |= self.attacks[square][piece.piece as usize];
+            for (t, threatening) in threatening.iter_mut().enumerate() {
+                *threatening |= self.attacked_from[square][t];
+            }
+            if self.fixed & 1 << square == 0 {
+                after[piece.piece as usize] &=
+                    u128::MAX.checked_shl(square as u32 + 1).unwrap_or(0);
+            }
+        }
+
+        let mut needed = [0usize; ChessPiece::ALL.len()];
+        for &chess_piece in pieces {
+            needed[chess_piece as usize] += 1;
+        }
+        let mut suitable = 0u128;
+        for t in 0..ChessPiece::ALL.len() {
+            if needed[t] == 0 {
+                continue;
+            }
+            let safe = after[t] & !occupied & !attacked & !threatening[t];
+            if (safe.count_ones() as usize) < needed[t] {
+                return false;
+            }
+            suitable |= safe;
+        }
+        suitable.count_ones() as usize >= pieces.len()
+    }
+
+    fn square(&self, piece: &Piece) -> usize {
+        (piece.row - 1) as usize * self.cols as usize + (piece.col - 1) as usize
+    }
+
+    fn piece(&self, square: usize, chess_piece: ChessPiece) -> Piece {
+        Piece {
+            row: (square / self.cols as usize) as i8 + 1,
+            col: (square % self.cols as usize) as i8 + 1,
+            piece: chess_piece,
+            colour: None,
+        }
+    }
+}
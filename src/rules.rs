@@ -0,0 +1,202 @@
+//! Variations on the rule that no piece may attack another one.
+//!
+//! The original puzzle forbids every attack between every two pieces, which is what
+//! [`Rules::default`] describes and what [`Board::is_safe`](crate::Board::is_safe)
+//! checks; only pieces given colours, which belong to armies, may attack pieces of
+//! their own colour. Variant puzzles relax it further, and pass their [`Rules`] to
+//! [`Board::is_safe_with`](crate::Board::is_safe_with) instead.
+
+use crate::notation::ParseError;
+use crate::{ChessPiece, Piece};
+use std::fmt;
+use std::str::FromStr;
+
+/// Which pairs of pieces must not attack each other.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum PairRule {
+    /// Every two pieces, whatever their colours.
+    AllPairs,
+    /// Only pieces of opposite colours, as with peaceable coexisting armies. Pieces of
+    /// the same colour may attack each other, while colourless pieces must keep the
+    /// peace with everyone, so this is the rule of the original puzzle for its
+    /// colourless pieces.
+    #[default]
+    OppositeColours,
+}
+
+impl PairRule {
+    /// Returns `true` if `a` and `b` must not attack each other under this rule.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::rules::PairRule;
+    /// use chess::{ChessPiece, Colour, Piece};
+    ///
+    /// let white = Piece { row: 1, col: 1, piece: ChessPiece::Queen, colour: Some(Colour::White) };
+    /// let other = Piece { row: 2, col: 2, ..white };
+    /// assert!(PairRule::AllPairs.applies(white, other));
+    /// assert!(!PairRule::OppositeColours.applies(white, other));
+    /// ```
+    pub fn applies(self, a: Piece, b: Piece) -> bool {
+        match self {
+            PairRule::AllPairs => true,
+            PairRule::OppositeColours => match (a.colour, b.colour) {
+                (Some(a), Some(b)) => a != b,
+                _ => true,
+            },
+        }
+    }
+}
+
+/// Which attacks are forbidden, by attacker type and victim type.
+///
+/// The default forbids every attack, which together with checking both directions
+/// gives the symmetric rule of the original puzzle. Allowing an attack in one direction
+/// only still forbids the other one, so allowing kings to attack queens changes nothing
+/// as long as queens may not attack kings.
+///
+/// The text form lists the allowed attacks as attacker and victim letters separated by
+/// commas, with `*` standing for every type: `K*,QN` allows kings to attack anything
+/// and queens to attack knights.
+///
+/// # Example
+///
+/// ```
+/// use chess::rules::AttackMatrix;
+/// use chess::ChessPiece;
+///
+/// let mut matrix = AttackMatrix::default();
+/// matrix.allow(ChessPiece::King, ChessPiece::Queen);
+/// assert!(!matrix.forbids(ChessPiece::King, ChessPiece::Queen));
+/// assert!(matrix.forbids(ChessPiece::Queen, ChessPiece::King));
+/// assert_eq!(matrix, "KQ".parse().unwrap());
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct AttackMatrix {
+    /// `forbidden[attacker][victim]`, indexed by piece type.
+    forbidden: [[bool; ChessPiece::ALL.len()]; ChessPiece::ALL.len()],
+}
+
+impl AttackMatrix {
+    /// Returns the matrix allowing every attack.
+    pub fn allow_all() -> AttackMatrix {
+        AttackMatrix {
+            forbidden: [[false; ChessPiece::ALL.len()]; ChessPiece::ALL.len()],
+        }
+    }
+
+    /// Returns `true` if pieces of type `attacker` may not attack pieces of type `victim`.
+    pub fn forbids(&self, attacker: ChessPiece, victim: ChessPiece) -> bool {
+        self.forbidden[attacker as usize][victim as usize]
+    }
+
+    /// Allows pieces of type `attacker` to attack pieces of type `victim`.
+    pub fn allow(&mut self, attacker: ChessPiece, victim: ChessPiece) {
+        self.forbidden[attacker as usize][victim as usize] = false;
+    }
+
+    /// Forbids pieces of type `attacker` to attack pieces of type `victim`.
+    pub fn forbid(&mut self, attacker: ChessPiece, victim: ChessPiece) {
+        self.forbidden[attacker as usize][victim as usize] = true;
+    }
+}
+
+impl Default for AttackMatrix {
+    fn default() -> AttackMatrix {
+        AttackMatrix {
+            forbidden: [[true; ChessPiece::ALL.len()]; ChessPiece::ALL.len()],
+        }
+    }
+}
+
+impl fmt::Display for AttackMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let allowed: Vec<String> = ChessPiece::ALL
+            .iter()
+            .flat_map(|&attacker| {
+                ChessPiece::ALL
+                    .iter()
+                    .map(move |&victim| (attacker, victim))
+            })
+            .filter(|&(attacker, victim)| !self.forbids(attacker, victim))
+            .map(|(attacker, victim)| format!("{}{}", attacker, victim))
+            .collect();
+        f.write_str(&allowed.join(","))
+    }
+}
+
+impl FromStr for AttackMatrix {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<AttackMatrix, ParseError> {
+        let mut matrix = AttackMatrix::default();
+        let types = |symbol: char| -> Result<Vec<ChessPiece>, ParseError> {
+            if symbol == '*' {
+                return Ok(ChessPiece::ALL.to_vec());
+            }
+            ChessPiece::from_symbol(symbol)
+                .map(|piece| vec![piece])
+                .ok_or_else(|| ParseError::new(format!("unknown piece `{}` in `{}`", symbol, s)))
+        };
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let symbols: Vec<char> = pair.chars().collect();
+            let [attacker, victim] = symbols[..] else {
+                return Err(ParseError::new(format!("invalid attack `{}`", pair)));
+            };
+            for attacker in types(attacker)? {
+                for &victim in &types(victim)? {
+                    matrix.allow(attacker, victim);
+                }
+            }
+        }
+        Ok(matrix)
+    }
+}
+
+/// The rules of a problem.
+///
+/// # Fields
+///
+/// * `pairs` - Which pairs of pieces must not attack each other
+/// * `no_pawns_on_back_ranks` - Whether pawns are kept off the first and last rows, as
+///   in a real game where they can neither stand behind their starting rank nor stay
+///   on the promotion rank
+/// * `max_attackers` - How many other pieces may attack each piece, for "k-attacking"
+///   problems; `0` forbids every attack
+/// * `attacks` - Which attacks are forbidden, by attacker and victim type
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct Rules {
+    pub pairs: PairRule,
+    pub no_pawns_on_back_ranks: bool,
+    pub max_attackers: usize,
+    pub attacks: AttackMatrix,
+}
+
+impl Rules {
+    /// Returns `true` if `piece` may stand on its square of a board with `rows` rows,
+    /// regardless of the other pieces.
+    pub fn may_stand(&self, piece: Piece, rows: i8) -> bool {
+        let back_rank = piece.row == 1 || piece.row == rows;
+        !(self.no_pawns_on_back_ranks && piece.piece == ChessPiece::Pawn && back_rank)
+    }
+
+    /// Returns `true` if `attacker` attacks `victim` and the rules count that attack.
+    ///
+    /// A piece never threatens itself, nor another piece on its own square.
+    pub fn threatens(&self, attacker: Piece, victim: Piece) -> bool {
+        (attacker.row, attacker.col) != (victim.row, victim.col)
+            && self.pairs.applies(attacker, victim)
+            && self.attacks.forbids(attacker.piece, victim.piece)
+            && attacker.attacks(victim)
+    }
+
+    /// Returns `true` if these rules forbid every attack between every two pieces but
+    /// those of the same colour, as in the original puzzle, whatever they say about
+    /// pawns on back ranks.
+    pub fn forbids_every_attack(&self) -> bool {
+        self.pairs == PairRule::OppositeColours
+            && self.max_attackers == 0
+            && self.attacks == AttackMatrix::default()
+    }
+}
@@ -0,0 +1,113 @@
+//! Uniform random sampling of solutions.
+//!
+//! Every solution is a single leaf of the duplicate-free search tree of
+//! [`crate::search`]. Walking down that tree and picking each child with probability
+//! proportional to the number of solutions below it therefore lands on every solution
+//! with the same probability.
+//!
+//! The subtree counts are exact, and each one comes from [`search::count`], which
+//! enumerates every solution below the subtree. The first sample therefore costs as
+//! much as counting all the solutions of the problem, and sampling is only practical
+//! for problems that can be counted that way. The counts are cached, so later samples
+//! only count the subtrees the earlier ones didn't go through.
+
+use crate::bigint::BigUint;
+use crate::rng::Rng;
+use crate::{search, Board, ChessPiece};
+use std::collections::HashMap;
+
+/// Draws solutions of a problem uniformly at random.
+///
+/// # Example
+///
+/// ```
+/// use chess::sample::Sampler;
+/// use chess::{Board, ChessPiece};
+///
+/// let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+/// let mut sampler = Sampler::new(Board::new(3, 3, Vec::new()), &pieces, 7);
+/// assert_eq!(sampler.total().to_u64(), Some(4));
+/// let board = sampler.sample().unwrap();
+/// assert_eq!(board.pieces.len(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sampler {
+    board: Board,
+    pieces: Vec<ChessPiece>,
+    rng: Rng,
+    counts: HashMap<Board, BigUint>,
+}
+
+impl Sampler {
+    /// Creates a sampler for the solutions obtained by placing `pieces` on `board`.
+    ///
+    /// Two samplers created with the same `seed` draw the same solutions.
+    pub fn new(board: Board, pieces: &[ChessPiece], seed: u64) -> Sampler {
+        Sampler {
+            board,
+            pieces: pieces.to_vec(),
+            rng: Rng::new(seed),
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of solutions of the problem.
+    ///
+    /// This enumerates all the solutions, unless a sample was drawn before.
+    pub fn total(&mut self) -> BigUint {
+        let board = self.board.clone();
+        self.children(&board, 0).1.iter().sum()
+    }
+
+    /// Draws one solution, or returns `None` if the problem has no solution.
+    ///
+    /// The pieces of the returned board are sorted, as with [`Board::place`].
+    pub fn sample(&mut self) -> Option<Board> {
+        let mut board = self.board.clone();
+        for next in 0..self.pieces.len() {
+            let (children, counts) = self.children(&board, next);
+            let total: BigUint = counts.iter().sum();
+            if total.is_zero() {
+                return None;
+            }
+            let target = total.random_below(&mut self.rng);
+            let mut below = BigUint::zero();
+            let chosen = counts
+                .iter()
+                .position(|count| {
+                    below += count;
+                    target < below
+                })
+                .expect("the target falls within one of the children");
+            board = children.into_iter().nth(chosen).expect("chosen child exists");
+        }
+        Some(board)
+    }
+
+    /// Returns the children of `board` in the search tree with the number of solutions
+    /// below each of them.
+    fn children(&mut self, board: &Board, next: usize) -> (Vec<Board>, Vec<BigUint>) {
+        if next == self.pieces.len() {
+            return (vec![board.clone()], vec![BigUint::from(1)]);
+        }
+        let children = search::children(board, &self.board.pieces, self.pieces[next]);
+        let counts = children
+            .iter()
+            .map(|child| self.subtree_count(child, next + 1))
+            .collect();
+        (children, counts)
+    }
+
+    /// Returns the number of solutions below `board`, enumerating them the first time.
+    fn subtree_count(&mut self, board: &Board, next: usize) -> BigUint {
+        if next == self.pieces.len() {
+            return BigUint::from(1);
+        }
+        if let Some(count) = self.counts.get(board) {
+            return count.clone();
+        }
+        let count = search::count_from(board, &self.board.pieces, &self.pieces[next..]);
+        self.counts.insert(board.clone(), count.clone());
+        count
+    }
+}
@@ -0,0 +1,302 @@
+//! Duplicate-free depth-first search over board configurations.
+//!
+//! [`solution`](crate::solution) reaches the same configuration through every order in
+//! which identical pieces can be placed, and relies on `tested_configurations` and the
+//! `solutions` set to discard the repeats. The search in this module never generates a
+//! repeat in the first place: a piece may only be placed on a square that comes after
+//! every piece of the same type it placed before (in row-major order). Each
+//! configuration therefore has exactly one parent, so configurations can be counted
+//! without being stored and the search can be stopped and resumed from its frontier.
+//! The pieces on the board when the search starts are fixed: they may stand anywhere,
+//! and the pieces placed by the search are not compared with them.
+
+use crate::bigint::BigUint;
+use crate::prune::ForwardChecker;
+use crate::rules::Rules;
+use crate::{Board, ChessPiece, Piece};
+
+/// Returns the boards obtained by placing `chess_piece` on every safe square of `board`
+/// that comes after all the pieces of the same type the search placed, that is all
+/// those but the `fixed` pieces the search started with.
+///
+/// # Example
+///
+/// ```
+/// use chess::{search, Board, ChessPiece};
+///
+/// let board = Board::new(2, 2, Vec::new());
+/// let kings = search::children(&board, &[], ChessPiece::King);
+/// assert_eq!(kings.len(), 4);
+/// // A second king can't be placed anywhere next to the first one.
+/// assert!(search::children(&kings[0], &[], ChessPiece::King).is_empty());
+/// // A king the search started with doesn't restrict the squares of the others.
+/// let board: Board = "3x3 K(3,3)".parse().unwrap();
+/// assert_eq!(search::children(&board, &board.pieces, ChessPiece::King).len(), 5);
+/// ```
+pub fn children(board: &Board, fixed: &[Piece], chess_piece: ChessPiece) -> Vec<Board> {
+    candidates(board, fixed, chess_piece)
+        .filter(|&piece| board.is_safe(piece))
+        .map(|piece| board.place(piece))
+        .collect()
+}
+
+/// Counts the configurations obtained by placing all of `pieces` on `board`.
+///
+/// Placing no pieces at all yields exactly one configuration, the board itself.
+///
+/// # Example
+///
+/// ```
+/// use chess::bigint::BigUint;
+/// use chess::{search, Board, ChessPiece};
+///
+/// let board = Board::new(3, 3, Vec::new());
+/// let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+/// assert_eq!(search::count(&board, &pieces), BigUint::from(4));
+/// ```
+pub fn count(board: &Board, pieces: &[ChessPiece]) -> BigUint {
+    count_from(board, &board.pieces, pieces)
+}
+
+/// Like [`count`], for a board reached by the search from one holding the `fixed`
+/// pieces.
+pub(crate) fn count_from(board: &Board, fixed: &[Piece], pieces: &[ChessPiece]) -> BigUint {
+    let mut total = 0u64;
+    for_each_solution_from(board, fixed, pieces, |_| total += 1);
+    BigUint::from(total)
+}
+
+/// Calls `visit` once for every configuration obtained by placing all of `pieces` on
+/// `board`.
+///
+/// The pieces of the boards passed to `visit` are not kept sorted; use
+/// [`Board::place`] or sort them before comparing boards.
+pub fn for_each_solution<F>(board: &Board, pieces: &[ChessPiece], visit: F)
+where
+    F: FnMut(&Board),
+{
+    for_each_solution_from(board, &board.pieces, pieces, visit);
+}
+
+/// Like [`for_each_solution`], for a board reached by the search from one holding the
+/// `fixed` pieces.
+pub(crate) fn for_each_solution_from<F>(
+    board: &Board,
+    fixed: &[Piece],
+    pieces: &[ChessPiece],
+    mut visit: F,
+) where
+    F: FnMut(&Board),
+{
+    for_each_node_from(board, fixed, pieces, |board, pieces| {
+        if pieces.is_empty() {
+            visit(board);
+        }
+    });
+}
+
+/// Counts the boards the search goes through while placing `pieces` on `board`: the
+/// starting board, every partial configuration it extends and every solution.
+///
+/// The order of `pieces` doesn't change the solutions but can change this number a lot,
+/// which makes it a fair way to compare [`PieceOrder`](crate::order::PieceOrder)s.
+///
+/// # Example
+///
+/// ```
+/// use chess::{search, Board, ChessPiece};
+///
+/// let board = Board::new(2, 2, Vec::new());
+/// // The empty board and the four boards with a king.
+/// assert_eq!(search::nodes(&board, &[ChessPiece::King]), 5);
+/// // No second king fits next to the first, so those four boards are never reached.
+/// assert_eq!(search::nodes(&board, &[ChessPiece::King, ChessPiece::King]), 1);
+/// ```
+pub fn nodes(board: &Board, pieces: &[ChessPiece]) -> u64 {
+    let mut nodes = 0;
+    for_each_node(board, pieces, |_, _| nodes += 1);
+    nodes
+}
+
+/// Calls `visit` with every board the search goes through and the pieces still to be
+/// placed on it, solutions included.
+///
+/// As with [`for_each_solution`], the pieces of the boards passed to `visit` are not
+/// kept sorted.
+pub fn for_each_node<F>(board: &Board, pieces: &[ChessPiece], visit: F)
+where
+    F: FnMut(&Board, &[ChessPiece]),
+{
+    for_each_node_from(board, &board.pieces, pieces, visit);
+}
+
+fn for_each_node_from<F>(board: &Board, fixed: &[Piece], pieces: &[ChessPiece], mut visit: F)
+where
+    F: FnMut(&Board, &[ChessPiece]),
+{
+    let checker = ForwardChecker::new(board.rows, board.cols).with_fixed(fixed);
+    let mut board = board.clone();
+    visit_nodes(&mut board, fixed, pieces, &checker, &mut visit);
+}
+
+fn visit_nodes<F>(
+    board: &mut Board,
+    fixed: &[Piece],
+    pieces: &[ChessPiece],
+    checker: &ForwardChecker,
+    visit: &mut F,
+) where
+    F: FnMut(&Board, &[ChessPiece]),
+{
+    visit(board, pieces);
+    if let Some((&chess_piece, tail)) = pieces.split_first() {
+        let safe: Vec<Piece> = candidates(board, fixed, chess_piece)
+            .filter(|&piece| board.is_safe(piece))
+            .collect();
+        for piece in safe {
+            board.pieces.push(piece);
+            if checker.fits(board, tail) {
+                visit_nodes(board, fixed, tail, checker, visit);
+            }
+            board.pieces.pop();
+        }
+    }
+}
+
+/// Counts the configurations obtained by placing all of `pieces` on `board` under
+/// `rules`.
+///
+/// # Example
+///
+/// ```
+/// use chess::rules::Rules;
+/// use chess::{search, Board, ChessPiece};
+///
+/// // Two rooks on a 1x2 board attack each other, which is fine if each may have one
+/// // attacker.
+/// let board = Board::new(1, 2, Vec::new());
+/// let rooks = [ChessPiece::Rook, ChessPiece::Rook];
+/// let rules = Rules { max_attackers: 1, ..Rules::default() };
+/// assert!(search::count(&board, &rooks).is_zero());
+/// assert_eq!(search::count_with(&board, &rooks, &rules).to_u64(), Some(1));
+/// ```
+pub fn count_with(board: &Board, pieces: &[ChessPiece], rules: &Rules) -> BigUint {
+    let mut total = 0u64;
+    for_each_solution_with(board, pieces, rules, |_| total += 1);
+    BigUint::from(total)
+}
+
+/// Calls `visit` once for every configuration obtained by placing all of `pieces` on
+/// `board` under `rules`.
+///
+/// The number of attackers of every piece is kept up to date as pieces are placed and
+/// removed, so checking a placement takes a single pass over the board whatever the
+/// number of attackers allowed. As with [`for_each_solution`], the pieces of the boards
+/// passed to `visit` are not kept sorted.
+pub fn for_each_solution_with<F>(board: &Board, pieces: &[ChessPiece], rules: &Rules, mut visit: F)
+where
+    F: FnMut(&Board),
+{
+    let fixed = board.pieces.clone();
+    let mut board = board.clone();
+    let mut attackers: Vec<usize> = board
+        .pieces
+        .iter()
+        .map(|&victim| {
+            board
+                .pieces
+                .iter()
+                .filter(|&&piece| rules.threatens(piece, victim))
+                .count()
+        })
+        .collect();
+    visit_solutions_with(
+        &mut board,
+        &fixed,
+        &mut attackers,
+        pieces,
+        rules,
+        &mut visit,
+    );
+}
+
+/// Like `visit_nodes`, calling `visit` on solutions only, with `attackers[i]` the number
+/// of pieces attacking `board.pieces[i]`.
+fn visit_solutions_with<F>(
+    board: &mut Board,
+    fixed: &[Piece],
+    attackers: &mut Vec<usize>,
+    pieces: &[ChessPiece],
+    rules: &Rules,
+    visit: &mut F,
+) where
+    F: FnMut(&Board),
+{
+    let Some((&chess_piece, tail)) = pieces.split_first() else {
+        visit(board);
+        return;
+    };
+    let candidates: Vec<Piece> = candidates(board, fixed, chess_piece)
+        .filter(|&piece| rules.may_stand(piece, board.rows))
+        .collect();
+    let mut victims = Vec::new();
+    for piece in candidates {
+        victims.clear();
+        let mut own_attackers = 0;
+        let mut safe = true;
+        for (i, &other) in board.pieces.iter().enumerate() {
+            if (other.row, other.col) == (piece.row, piece.col) {
+                safe = false;
+                break;
+            }
+            if rules.threatens(other, piece) {
+                own_attackers += 1;
+            }
+            if rules.threatens(piece, other) {
+                if attackers[i] == rules.max_attackers {
+                    safe = false;
+                    break;
+                }
+                victims.push(i);
+            }
+        }
+        if !safe || own_attackers > rules.max_attackers {
+            continue;
+        }
+        for &i in &victims {
+            attackers[i] += 1;
+        }
+        board.pieces.push(piece);
+        attackers.push(own_attackers);
+        visit_solutions_with(board, fixed, attackers, tail, rules, visit);
+        attackers.pop();
+        board.pieces.pop();
+        for &i in &victims {
+            attackers[i] -= 1;
+        }
+    }
+}
+
+/// Returns the squares of `board` that come after every piece of type `chess_piece`
+/// on it but the `fixed` ones, as pieces of that type.
+pub(crate) fn candidates<'a>(
+    board: &'a Board,
+    fixed: &[Piece],
+    chess_piece: ChessPiece,
+) -> impl Iterator<Item = Piece> + 'a {
+    let last = board
+        .pieces
+        .iter()
+        .filter(|piece| piece.piece == chess_piece && !fixed.contains(piece))
+        .map(|piece| (piece.row, piece.col))
+        .max();
+    (1..=board.rows)
+        .flat_map(move |row| (1..=board.cols).map(move |col| (row, col)))
+        .filter(move |&square| last.is_none_or(|last| square > last))
+        .map(move |(row, col)| Piece {
+            row,
+            col,
+            piece: chess_piece,
+            colour: None,
+        })
+}
@@ -0,0 +1,114 @@
+//! Splitting a problem into independent shards.
+//!
+//! The search tree of [`crate::search`] is expanded breadth-first from the starting
+//! board until it has at least as many boards as there are shards (or every piece is placed).
+//! The boards of that level are dealt to the shards in turn, so shard `i` of `K` gets
+//! boards `i`, `i + K`, `i + 2K`, ... The order of the search is deterministic, which
+//! lets every machine compute its own share without talking to the others, and the
+//! shard counts add up to the count of the whole problem.
+//!
+//! Each shard writes its result as a solution file whose first line is a `# count N`
+//! header, and [`merge`] adds those results back together.
+
+use crate::bigint::BigUint;
+use crate::checkpoint::Checkpoint;
+use crate::notation::{read_solutions, write_solutions, ParseError};
+use crate::{search, Board, ChessPiece};
+use std::io::{self, BufRead, Write};
+
+const COUNT_HEADER: &str = "# count ";
+
+/// Returns the search of shard `index` out of `of` as a [`Checkpoint`], ready to be run
+/// or saved.
+///
+/// # Panics
+///
+/// Panics if `of` is zero or `index` is not smaller than `of`.
+///
+/// # Example
+///
+/// ```
+/// use chess::bigint::BigUint;
+/// use chess::{shard, Board, ChessPiece};
+///
+/// let board = Board::new(3, 3, Vec::new());
+/// let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+/// let total: BigUint = (0..4)
+///     .map(|index| {
+///         let mut checkpoint = shard::shard(&board, &pieces, index, 4);
+///         checkpoint.run(u64::MAX);
+///         checkpoint.solutions
+///     })
+///     .sum();
+/// assert_eq!(total, BigUint::from(4));
+/// ```
+pub fn shard(board: &Board, pieces: &[ChessPiece], index: usize, of: usize) -> Checkpoint {
+    assert!(index < of, "shard index {} out of range 0..{}", index, of);
+    let mut level = vec![board.clone()];
+    let mut next = 0;
+    while level.len() < of && next < pieces.len() {
+        level = level
+            .iter()
+            .flat_map(|child| search::children(child, &board.pieces, pieces[next]))
+            .collect();
+        next += 1;
+    }
+    let mut checkpoint = Checkpoint::new(board.clone(), pieces);
+    checkpoint.frontier = level
+        .into_iter()
+        .skip(index)
+        .step_by(of)
+        .map(|board| (board, next))
+        .rev()
+        .collect();
+    checkpoint
+}
+
+/// Writes the result of a shard: a `# count N` header followed by its solutions, if
+/// they were enumerated.
+pub fn write_result<W: Write>(mut writer: W, count: &BigUint, boards: &[Board]) -> io::Result<()> {
+    writeln!(writer, "{}{}", COUNT_HEADER, count)?;
+    write_solutions(writer, boards)
+}
+
+/// Reads a result written by [`write_result`], or a plain solution file.
+///
+/// # Returns
+///
+/// The count given in the `# count N` header, or the number of boards when there's no
+/// header, together with the boards of the file
+pub fn read_result<R: BufRead>(reader: R) -> io::Result<(BigUint, Vec<Board>)> {
+    let mut count = None;
+    let mut boards = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if let Some(value) = line.strip_prefix(COUNT_HEADER) {
+            let value = value
+                .trim()
+                .parse()
+                .map_err(|_| ParseError::new(format!("invalid count header `{}`", line)))?;
+            count = Some(value);
+        } else {
+            boards.append(&mut read_solutions(line.as_bytes())?);
+        }
+    }
+    let count = count.unwrap_or_else(|| BigUint::from(boards.len() as u64));
+    Ok((count, boards))
+}
+
+/// Merges the results of several shards, adding up their counts and concatenating their
+/// solutions.
+pub fn merge<R, I>(results: I) -> io::Result<(BigUint, Vec<Board>)>
+where
+    R: BufRead,
+    I: IntoIterator<Item = R>,
+{
+    let mut total = BigUint::zero();
+    let mut boards = Vec::new();
+    for result in results {
+        let (count, mut shard_boards) = read_result(result)?;
+        total += &count;
+        boards.append(&mut shard_boards);
+    }
+    Ok((total, boards))
+}
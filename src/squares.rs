@@ -0,0 +1,76 @@
+//! Which squares of a board are attacked, and by whom.
+//!
+//! These queries look at the board as it stands, with [`Piece::attacks`] deciding what
+//! each piece attacks, and are meant for rendering, hints and pruning. A piece doesn't
+//! attack its own square here, although [`Piece::attacks`] says it does so that two
+//! pieces can never share a square.
+
+use crate::{Board, Piece};
+
+impl Board {
+    /// Returns every square of the board in row-major order.
+    pub fn squares(&self) -> impl Iterator<Item = (i8, i8)> {
+        let cols = self.cols;
+        (1..=self.rows).flat_map(move |row| (1..=cols).map(move |col| (row, col)))
+    }
+
+    /// Returns the pieces attacking the square at `row`, `col`, in board order.
+    ///
+    /// A piece standing on the square is not one of its attackers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::Board;
+    ///
+    /// let board: Board = "3x3 R(1,1) K(3,3) N(2,2)".parse().unwrap();
+    /// let attackers: Vec<String> = board.attackers_of(3, 2).map(|p| p.to_string()).collect();
+    /// assert_eq!(attackers, ["K(3,3)"]);
+    /// assert_eq!(board.attackers_of(1, 3).count(), 1);
+    /// ```
+    pub fn attackers_of(&self, row: i8, col: i8) -> impl Iterator<Item = Piece> + '_ {
+        self.pieces.iter().copied().filter(move |&piece| {
+            (piece.row, piece.col) != (row, col) && piece.attacks(Piece { row, col, ..piece })
+        })
+    }
+
+    /// Returns the squares attacked by at least one piece, in row-major order.
+    ///
+    /// Occupied squares are included when another piece attacks them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::Board;
+    ///
+    /// let board: Board = "3x3 R(1,1)".parse().unwrap();
+    /// let attacked: Vec<(i8, i8)> = board.attacked_squares().collect();
+    /// assert_eq!(attacked, [(1, 2), (1, 3), (2, 1), (3, 1)]);
+    /// ```
+    pub fn attacked_squares(&self) -> impl Iterator<Item = (i8, i8)> + '_ {
+        self.squares()
+            .filter(move |&(row, col)| self.attackers_of(row, col).next().is_some())
+    }
+
+    /// Returns the squares that are neither occupied nor attacked, in row-major order.
+    ///
+    /// A piece placed on a free square is not attacked, but it may still attack others;
+    /// use [`Board::is_safe`] to check both.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::Board;
+    ///
+    /// let board: Board = "3x3 R(1,1)".parse().unwrap();
+    /// assert_eq!(board.free_squares().count(), 4);
+    /// ```
+    pub fn free_squares(&self) -> impl Iterator<Item = (i8, i8)> + '_ {
+        self.squares().filter(move |&(row, col)| {
+            self.pieces
+                .iter()
+                .all(|piece| (piece.row, piece.col) != (row, col))
+                && self.attackers_of(row, col).next().is_none()
+        })
+    }
+}
@@ -0,0 +1,248 @@
+//! Statistics on a run of [`solution_with_stats`](crate::solution_with_stats).
+//!
+//! They show where the search spends its effort: how many boards it expands, how many
+//! placements it checks, how much the `tested_configurations` cache and forward checking
+//! save, and how large the queue and the sets grow. The queue is processed in order of
+//! depth, so the time spent on each depth is measured as a whole.
+
+use crate::{Board, ChessPiece, Piece};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::mem;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// What the search did at one depth, the number of pieces on the boards it expanded.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct DepthStats {
+    /// Boards taken from the queue.
+    pub expanded: u64,
+    /// Time spent expanding them.
+    pub time: Duration,
+}
+
+/// Statistics on a search.
+///
+/// # Example
+///
+/// ```
+/// use chess::{solution_with_stats, Board, ChessPiece};
+/// use std::collections::{HashSet, VecDeque};
+/// use std::rc::Rc;
+///
+/// let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+/// let mut board_stack: VecDeque<(Rc<Board>, &[ChessPiece])> = VecDeque::new();
+/// board_stack.push_front((Rc::new(Board::new(3, 3, Vec::new())), &pieces));
+/// let mut solutions = HashSet::new();
+/// let (_, stats) = solution_with_stats(&mut board_stack, &mut solutions, &mut HashSet::new());
+/// assert_eq!(solutions.len(), 4);
+/// // The empty board is expanded at depth 0, then the boards with one king at depth 1.
+/// assert_eq!(stats.depths[0].expanded, 1);
+/// assert_eq!(stats.expanded, stats.depths.iter().map(|depth| depth.expanded).sum::<u64>());
+/// assert_eq!(stats.safety_checks, 9 * stats.expanded);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct SearchStats {
+    /// Boards taken from the queue and expanded.
+    pub expanded: u64,
+    /// Placements checked with [`Board::is_safe`].
+    pub safety_checks: u64,
+    /// Boards dropped because `tested_configurations` already held them.
+    pub duplicates: u64,
+    /// Boards dropped by forward checking.
+    pub pruned: u64,
+    /// The largest number of boards waiting in the queue at once.
+    pub max_frontier: usize,
+    /// An estimate of the most memory held at once by the queue and the sets, in bytes,
+    /// sampled after each board is expanded.
+    pub peak_memory: usize,
+    /// The statistics of each depth, indexed by the number of pieces on the board.
+    pub depths: Vec<DepthStats>,
+}
+
+impl SearchStats {
+    /// Counts a board expanded at `depth`.
+    pub(crate) fn expand(&mut self, depth: usize) {
+        self.depth(depth).expanded += 1;
+        self.expanded += 1;
+    }
+
+    /// Adds `time` spent expanding boards at `depth`.
+    pub(crate) fn spend(&mut self, depth: usize, time: Duration) {
+        self.depth(depth).time += time;
+    }
+
+    fn depth(&mut self, depth: usize) -> &mut DepthStats {
+        if self.depths.len() <= depth {
+            self.depths.resize(depth + 1, DepthStats::default());
+        }
+        &mut self.depths[depth]
+    }
+}
+
+/// Receives what the search does as it goes.
+///
+/// Every method does nothing by default: [`solution`](crate::solution) reports to `()`,
+/// so it pays for none of the bookkeeping, and
+/// [`solution_with_stats`](crate::solution_with_stats) reports to a [`Recorder`].
+pub(crate) trait Sink {
+    /// A board is taken from the queue to be expanded.
+    fn expand(&mut self, _board: &Board) {}
+
+    /// A placement is checked with [`Board::is_safe`].
+    fn check(&mut self) {}
+
+    /// A board is dropped because `tested_configurations` already held it.
+    fn duplicate(&mut self) {}
+
+    /// A board is dropped by forward checking.
+    fn prune(&mut self) {}
+
+    /// A new board is stored in the queue or the sets.
+    fn store(&mut self, _board: &Rc<Board>) {}
+
+    /// A board is pushed, leaving `len` boards in the queue.
+    fn push(&mut self, _len: usize) {}
+
+    /// `board` is done being expanded.
+    fn expanded(
+        &mut self,
+        _board: &Rc<Board>,
+        _board_stack: &VecDeque<(Rc<Board>, &[ChessPiece])>,
+        _solutions: &HashSet<Rc<Board>>,
+        _tested_configurations: &HashSet<Rc<Board>>,
+    ) {
+    }
+}
+
+impl Sink for () {}
+
+/// Collects [`SearchStats`] from what the search reports.
+pub(crate) struct Recorder {
+    stats: SearchStats,
+    /// The depth of the last board expanded, and when its expansion started.
+    clock: Option<(usize, Instant)>,
+    /// The bytes of the boards held by the queue and the sets.
+    board_bytes: usize,
+}
+
+impl Recorder {
+    /// Starts recording a search that begins with `board_stack`.
+    pub(crate) fn new(board_stack: &VecDeque<(Rc<Board>, &[ChessPiece])>) -> Recorder {
+        Recorder {
+            stats: SearchStats {
+                max_frontier: board_stack.len(),
+                ..SearchStats::default()
+            },
+            clock: None,
+            board_bytes: board_stack
+                .iter()
+                .map(|(board, _)| board_bytes(board))
+                .sum(),
+        }
+    }
+
+    /// Returns the statistics of the finished search.
+    pub(crate) fn finish(mut self) -> SearchStats {
+        if let Some((depth, start)) = self.clock {
+            self.stats.spend(depth, start.elapsed());
+        }
+        self.stats
+    }
+}
+
+impl Sink for Recorder {
+    fn expand(&mut self, board: &Board) {
+        let now = Instant::now();
+        if let Some((depth, start)) = self.clock {
+            self.stats.spend(depth, now - start);
+        }
+        self.clock = Some((board.pieces.len(), now));
+        self.stats.expand(board.pieces.len());
+    }
+
+    fn check(&mut self) {
+        self.stats.safety_checks += 1;
+    }
+
+    fn duplicate(&mut self) {
+        self.stats.duplicates += 1;
+    }
+
+    fn prune(&mut self) {
+        self.stats.pruned += 1;
+    }
+
+    fn store(&mut self, board: &Rc<Board>) {
+        self.board_bytes += board_bytes(board);
+    }
+
+    fn push(&mut self, len: usize) {
+        self.stats.max_frontier = self.stats.max_frontier.max(len);
+    }
+
+    fn expanded(
+        &mut self,
+        board: &Rc<Board>,
+        board_stack: &VecDeque<(Rc<Board>, &[ChessPiece])>,
+        solutions: &HashSet<Rc<Board>>,
+        tested_configurations: &HashSet<Rc<Board>>,
+    ) {
+        let tables = table_bytes(board_stack, solutions, tested_configurations);
+        self.stats.peak_memory = self.stats.peak_memory.max(tables + self.board_bytes);
+        // Only the starting boards are held by nothing but the queue.
+        if Rc::strong_count(board) == 1 {
+            self.board_bytes -= board_bytes(board);
+        }
+    }
+}
+
+/// Estimates the bytes of a board held through an [`Rc`], which carries two counts.
+///
+/// Boards are shared between the queue and the sets, so each one is counted once, when
+/// it is first stored.
+fn board_bytes(board: &Rc<Board>) -> usize {
+    2 * mem::size_of::<usize>()
+        + mem::size_of::<Board>()
+        + board.pieces.capacity() * mem::size_of::<Piece>()
+}
+
+/// Estimates the bytes of the tables of `board_stack`, `solutions` and
+/// `tested_configurations`, leaving out the boards they hold.
+fn table_bytes(
+    board_stack: &VecDeque<(Rc<Board>, &[ChessPiece])>,
+    solutions: &HashSet<Rc<Board>>,
+    tested_configurations: &HashSet<Rc<Board>>,
+) -> usize {
+    // A hash set keeps one control byte per slot.
+    let set_bytes = |set: &HashSet<Rc<Board>>| set.capacity() * (mem::size_of::<Rc<Board>>() + 1);
+    board_stack.capacity() * mem::size_of::<(Rc<Board>, &[ChessPiece])>()
+        + set_bytes(solutions)
+        + set_bytes(tested_configurations)
+}
+
+impl fmt::Display for SearchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Boards expanded      {}", self.expanded)?;
+        writeln!(f, "Safety checks        {}", self.safety_checks)?;
+        writeln!(f, "Duplicates rejected  {}", self.duplicates)?;
+        writeln!(f, "Boards pruned        {}", self.pruned)?;
+        writeln!(f, "Largest frontier     {}", self.max_frontier)?;
+        writeln!(
+            f,
+            "Peak memory          {:.1} MiB (estimate)",
+            self.peak_memory as f64 / (1 << 20) as f64
+        )?;
+        write!(f, "Depth  Expanded  Time")?;
+        for (depth, stats) in self.depths.iter().enumerate() {
+            write!(
+                f,
+                "\n{:<5}  {:<8}  {:.3}s",
+                depth,
+                stats.expanded,
+                stats.time.as_secs_f64()
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,397 @@
+//! A queryable store of saved solutions.
+//!
+//! A [`Store`] loads solution files, as text or in the format of [`crate::binary`], and
+//! keeps their boards packed. A [`Query`] selects boards by where their pieces stand: a
+//! list of filters, each asking for some, all or none of the pieces of a type to stand
+//! in a [`Region`], that a board must all pass. Queries are written one filter after the
+//! other, separated by spaces:
+//!
+//! ```text
+//! Q(4,4) all:K(edge) no:N(1,_) _(2..3,1..2)
+//! ```
+//!
+//! reads "a queen on (4,4), both kings on the edge, no knight on row 1 and some piece in
+//! rows 2 to 3 of columns 1 to 2". A filter is a piece letter, or `_` for any piece,
+//! followed by a square where a row or column can be a number, a range `a..b` or `_` for
+//! any, or by `(edge)`. It is prefixed by `all:` or `no:` to constrain all the pieces
+//! instead of some.
+
+use crate::binary::{self, MAGIC};
+use crate::notation::{read_solutions, ParseError};
+use crate::packed::{PackError, PackedBoard};
+use crate::{Board, ChessPiece, Piece};
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// A set of squares of a board.
+///
+/// * `Area` - The squares in the given rows and columns
+/// * `Edge` - The first and last rows and columns
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum Region {
+    Area {
+        rows: RangeInclusive<i8>,
+        cols: RangeInclusive<i8>,
+    },
+    Edge,
+}
+
+/// How many of the pieces a filter looks at must stand in its region.
+///
+/// * `Any` - At least one
+/// * `All` - Every one, which holds when there are none
+/// * `No` - None
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum Quantifier {
+    #[default]
+    Any,
+    All,
+    No,
+}
+
+/// A condition on where the pieces of a type, or all the pieces, stand.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Filter {
+    pub quantifier: Quantifier,
+    /// The type of the pieces looked at, or `None` for every piece.
+    pub piece: Option<ChessPiece>,
+    pub region: Region,
+}
+
+/// Filters that a board must all pass. The empty query selects every board.
+///
+/// # Example
+///
+/// ```
+/// use chess::store::Query;
+/// use chess::Board;
+///
+/// let query: Query = "Q(4,4) all:K(edge)".parse().unwrap();
+/// let board: Board = "7x7 K(1,1) K(3,7) Q(4,4)".parse().unwrap();
+/// assert!(query.matches(&board));
+/// let board: Board = "7x7 K(1,1) K(3,6) Q(4,4)".parse().unwrap();
+/// assert!(!query.matches(&board));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct Query {
+    pub filters: Vec<Filter>,
+}
+
+/// Saved solutions, kept packed, to select boards from.
+///
+/// # Example
+///
+/// ```
+/// use chess::store::{Filter, Query, Region, Store};
+/// use chess::{search, Board, ChessPiece};
+///
+/// let mut store = Store::new();
+/// let queens = vec![ChessPiece::Queen; 8];
+/// search::for_each_solution(&Board::new(8, 8, Vec::new()), &queens, |solution| {
+///     store.insert(solution).unwrap();
+/// });
+/// assert_eq!(store.len(), 92);
+/// // The solutions with a queen on (1,1).
+/// let corner = Filter::any(Some(ChessPiece::Queen), Region::square(1, 1));
+/// assert_eq!(store.count(&Query::new(vec![corner])), 4);
+/// // Those with no queen in the four central squares.
+/// let query = "no:Q(4..5,4..5)".parse().unwrap();
+/// assert_eq!(store.select(&query).count(), 60);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Store {
+    boards: Vec<PackedBoard>,
+}
+
+impl Region {
+    /// Returns the single square `(row, col)`.
+    pub fn square(row: i8, col: i8) -> Region {
+        Region::area(row..=row, col..=col)
+    }
+
+    /// Returns the squares of `row`.
+    pub fn row(row: i8) -> Region {
+        Region::area(row..=row, 1..=i8::MAX)
+    }
+
+    /// Returns the squares of `col`.
+    pub fn col(col: i8) -> Region {
+        Region::area(1..=i8::MAX, col..=col)
+    }
+
+    /// Returns the squares in `rows` and `cols`.
+    pub fn area(rows: RangeInclusive<i8>, cols: RangeInclusive<i8>) -> Region {
+        Region::Area { rows, cols }
+    }
+
+    /// Returns `true` if `(row, col)` of a `rows`×`cols` board is in the region.
+    pub fn contains(&self, (rows, cols): (i8, i8), row: i8, col: i8) -> bool {
+        match self {
+            Region::Area {
+                rows: in_rows,
+                cols: in_cols,
+            } => in_rows.contains(&row) && in_cols.contains(&col),
+            Region::Edge => row == 1 || row == rows || col == 1 || col == cols,
+        }
+    }
+}
+
+impl Filter {
+    /// Returns a filter asking for at least one of the pieces of type `piece`, or any
+    /// piece, in `region`.
+    pub fn any(piece: Option<ChessPiece>, region: Region) -> Filter {
+        Filter {
+            quantifier: Quantifier::Any,
+            piece,
+            region,
+        }
+    }
+
+    /// Returns a filter asking for all the pieces of type `piece`, or all the pieces, to
+    /// stand in `region`.
+    pub fn all(piece: Option<ChessPiece>, region: Region) -> Filter {
+        Filter {
+            quantifier: Quantifier::All,
+            piece,
+            region,
+        }
+    }
+
+    /// Returns a filter asking for none of the pieces of type `piece`, or no piece at
+    /// all, to stand in `region`.
+    pub fn none(piece: Option<ChessPiece>, region: Region) -> Filter {
+        Filter {
+            quantifier: Quantifier::No,
+            piece,
+            region,
+        }
+    }
+
+    /// Returns `true` if the pieces of a board of the given dimensions pass the filter.
+    fn passes(&self, dimensions: (i8, i8), pieces: impl Iterator<Item = Piece>) -> bool {
+        let mut inside = pieces
+            .filter(|piece| {
+                self.piece
+                    .is_none_or(|chess_piece| piece.piece == chess_piece)
+            })
+            .map(|piece| self.region.contains(dimensions, piece.row, piece.col));
+        match self.quantifier {
+            Quantifier::Any => inside.any(|inside| inside),
+            Quantifier::All => inside.all(|inside| inside),
+            Quantifier::No => !inside.any(|inside| inside),
+        }
+    }
+}
+
+impl Query {
+    /// Creates a query from its filters.
+    pub fn new(filters: Vec<Filter>) -> Query {
+        Query { filters }
+    }
+
+    /// Returns `true` if `board` passes every filter.
+    pub fn matches(&self, board: &Board) -> bool {
+        self.filters
+            .iter()
+            .all(|filter| filter.passes((board.rows, board.cols), board.pieces.iter().copied()))
+    }
+
+    fn matches_packed(&self, board: &PackedBoard) -> bool {
+        self.filters
+            .iter()
+            .all(|filter| filter.passes(board.dimensions(), board.pieces()))
+    }
+}
+
+impl Store {
+    /// Creates an empty store.
+    pub fn new() -> Store {
+        Store::default()
+    }
+
+    /// Adds a board to the store.
+    pub fn insert(&mut self, board: &Board) -> Result<(), PackError> {
+        self.boards.push(PackedBoard::try_from(board)?);
+        Ok(())
+    }
+
+    /// Adds the boards of a solution file, text or binary, returning how many there
+    /// were. Nothing is added when the file is invalid.
+    pub fn load<R: BufRead>(&mut self, mut reader: R) -> io::Result<usize> {
+        let boards = if reader.fill_buf()?.starts_with(MAGIC) {
+            binary::Reader::new(reader)?.collect::<io::Result<Vec<_>>>()?
+        } else {
+            read_solutions(reader)?
+                .iter()
+                .map(PackedBoard::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        };
+        self.boards.extend(&boards);
+        Ok(boards.len())
+    }
+
+    /// Returns the number of boards in the store.
+    pub fn len(&self) -> usize {
+        self.boards.len()
+    }
+
+    /// Returns `true` if the store holds no boards.
+    pub fn is_empty(&self) -> bool {
+        self.boards.is_empty()
+    }
+
+    /// Returns the boards matching `query`, in the order they were added, with their
+    /// pieces sorted.
+    pub fn select<'a>(&'a self, query: &'a Query) -> impl Iterator<Item = Board> + 'a {
+        self.boards
+            .iter()
+            .filter(|board| query.matches_packed(board))
+            .map(PackedBoard::unpack)
+    }
+
+    /// Counts the boards matching `query`.
+    pub fn count(&self, query: &Query) -> usize {
+        self.boards
+            .iter()
+            .filter(|board| query.matches_packed(board))
+            .count()
+    }
+}
+
+impl FromIterator<PackedBoard> for Store {
+    fn from_iter<I: IntoIterator<Item = PackedBoard>>(boards: I) -> Store {
+        Store {
+            boards: boards.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<PackedBoard> for Store {
+    fn extend<I: IntoIterator<Item = PackedBoard>>(&mut self, boards: I) {
+        self.boards.extend(boards);
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Region::Area { rows, cols } => {
+                write!(f, "({},{})", Span(rows), Span(cols))
+            }
+            Region::Edge => f.write_str("(edge)"),
+        }
+    }
+}
+
+/// Writes a range of rows or columns as it is parsed.
+struct Span<'a>(&'a RangeInclusive<i8>);
+
+impl fmt::Display for Span<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (start, end) = (*self.0.start(), *self.0.end());
+        if (start, end) == (1, i8::MAX) {
+            f.write_str("_")
+        } else if start == end {
+            write!(f, "{}", start)
+        } else {
+            write!(f, "{}..{}", start, end)
+        }
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.quantifier {
+            Quantifier::Any => {}
+            Quantifier::All => f.write_str("all:")?,
+            Quantifier::No => f.write_str("no:")?,
+        }
+        match self.piece {
+            Some(chess_piece) => write!(f, "{}", chess_piece.symbol())?,
+            None => f.write_str("_")?,
+        }
+        write!(f, "{}", self.region)
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, filter) in self.filters.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", filter)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Filter {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Filter, ParseError> {
+        let invalid = || ParseError::new(format!("invalid filter `{}`", s));
+        let (quantifier, rest) = if let Some(rest) = s.strip_prefix("all:") {
+            (Quantifier::All, rest)
+        } else if let Some(rest) = s.strip_prefix("no:") {
+            (Quantifier::No, rest)
+        } else {
+            (Quantifier::Any, s)
+        };
+        let mut chars = rest.chars();
+        let piece = match chars.next().ok_or_else(invalid)? {
+            '_' => None,
+            symbol => Some(ChessPiece::from_symbol(symbol).ok_or_else(invalid)?),
+        };
+        let region = chars
+            .as_str()
+            .strip_prefix('(')
+            .and_then(|region| region.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+        let region = if region == "edge" {
+            Region::Edge
+        } else {
+            let (rows, cols) = region.split_once(',').ok_or_else(invalid)?;
+            Region::area(
+                parse_span(rows).ok_or_else(invalid)?,
+                parse_span(cols).ok_or_else(invalid)?,
+            )
+        };
+        Ok(Filter {
+            quantifier,
+            piece,
+            region,
+        })
+    }
+}
+
+/// Parses a number, a range `a..b` with `a <= b` or `_`.
+fn parse_span(s: &str) -> Option<RangeInclusive<i8>> {
+    if s == "_" {
+        return Some(1..=i8::MAX);
+    }
+    match s.split_once("..") {
+        Some((start, end)) => {
+            let span = start.parse().ok()?..=end.parse().ok()?;
+            (!span.is_empty()).then_some(span)
+        }
+        None => {
+            let line: i8 = s.parse().ok()?;
+            Some(line..=line)
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Query, ParseError> {
+        s.split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<Filter>, ParseError>>()
+            .map(Query::new)
+    }
+}
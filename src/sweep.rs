@@ -0,0 +1,180 @@
+//! Tables of solution counts over ranges of board sizes and piece counts.
+//!
+//! A [`Sweep`] counts the solutions of every problem made of a board size from a range
+//! of rows and columns and a number of copies of a pattern of pieces, e.g. 0 to 8 kings
+//! on every board from 1x1 to 8x8. Two shortcuts keep large sweeps cheap:
+//!
+//! * Without pawns the pieces attack the same way once the board is transposed, so a
+//!   `c`×`r` board has as many solutions as an `r`×`c` one and is counted once.
+//! * Removing one copy of the pattern from a solution leaves a solution, so once a
+//!   number of copies has no solution, no larger number has any.
+
+use crate::backend::Backend;
+use crate::bigint::BigUint;
+use crate::notation::format_pieces;
+use crate::{Board, ChessPiece};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// The problems of a sweep.
+///
+/// # Example
+///
+/// ```
+/// use chess::sweep::Sweep;
+/// use chess::ChessPiece;
+///
+/// // Non-attacking kings on the 1x1 to 3x3 square boards.
+/// let sweep = Sweep::new(1..=3, 1..=3, vec![ChessPiece::King], 0..=2).square();
+/// let table = sweep.run();
+/// assert_eq!(
+///     table.to_csv(),
+///     "rows,cols,0,1,2\n1,1,1,1,0\n2,2,1,4,0\n3,3,1,9,16\n"
+/// );
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Sweep {
+    pub rows: RangeInclusive<i8>,
+    pub cols: RangeInclusive<i8>,
+    /// The pieces placed `count` times over, for every count of `counts`.
+    pub pattern: Vec<ChessPiece>,
+    pub counts: RangeInclusive<usize>,
+    /// Whether only boards with as many rows as columns are counted.
+    pub square_only: bool,
+    pub backend: Backend,
+}
+
+/// The counts of a sweep, one row per board size and one column per number of copies of
+/// the pattern.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Table {
+    pub pattern: Vec<ChessPiece>,
+    pub counts: Vec<usize>,
+    /// The board sizes, in sweep order, with the solution count of each number of
+    /// copies.
+    pub rows: Vec<(i8, i8, Vec<BigUint>)>,
+    /// The number of problems solved, as opposed to answered from a transposed board or
+    /// a smaller count without solutions.
+    pub solved: usize,
+}
+
+impl Sweep {
+    /// Creates a sweep over every board size of `rows`×`cols` with the backtracking
+    /// backend.
+    pub fn new(
+        rows: RangeInclusive<i8>,
+        cols: RangeInclusive<i8>,
+        pattern: Vec<ChessPiece>,
+        counts: RangeInclusive<usize>,
+    ) -> Sweep {
+        Sweep {
+            rows,
+            cols,
+            pattern,
+            counts,
+            square_only: false,
+            backend: Backend::default(),
+        }
+    }
+
+    /// Restricts the sweep to square boards.
+    pub fn square(self) -> Sweep {
+        Sweep {
+            square_only: true,
+            ..self
+        }
+    }
+
+    /// Counts the solutions of every problem of the sweep.
+    pub fn run(&self) -> Table {
+        let symmetric = !self.pattern.contains(&ChessPiece::Pawn);
+        let mut cache: HashMap<(i8, i8), Vec<BigUint>> = HashMap::new();
+        let mut table = Table {
+            pattern: self.pattern.clone(),
+            counts: self.counts.clone().collect(),
+            rows: Vec::new(),
+            solved: 0,
+        };
+        for rows in self.rows.clone() {
+            for cols in self.cols.clone() {
+                if self.square_only && rows != cols {
+                    continue;
+                }
+                let key = if symmetric {
+                    (rows.min(cols), rows.max(cols))
+                } else {
+                    (rows, cols)
+                };
+                let counts = cache
+                    .entry(key)
+                    .or_insert_with(|| self.count_all(rows, cols, &mut table.solved));
+                table.rows.push((rows, cols, counts.clone()));
+            }
+        }
+        table
+    }
+
+    /// Counts the solutions on a `rows`×`cols` board for every number of copies, adding
+    /// the problems it solves to `solved`.
+    fn count_all(&self, rows: i8, cols: i8, solved: &mut usize) -> Vec<BigUint> {
+        let board = Board::new(rows, cols, Vec::new());
+        let mut none = false;
+        self.counts
+            .clone()
+            .map(|count| {
+                if none {
+                    return BigUint::default();
+                }
+                let pieces: Vec<ChessPiece> = (0..count)
+                    .flat_map(|_| self.pattern.iter().copied())
+                    .collect();
+                let solutions = self.backend.count(&board, &pieces);
+                *solved += 1;
+                none = solutions.is_zero();
+                solutions
+            })
+            .collect()
+    }
+}
+
+impl Table {
+    /// Writes the table as comma-separated values, with a header line.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("rows,cols");
+        for count in &self.counts {
+            let _ = write!(csv, ",{}", count);
+        }
+        csv.push('\n');
+        for (rows, cols, counts) in &self.rows {
+            let _ = write!(csv, "{},{}", rows, cols);
+            for count in counts {
+                let _ = write!(csv, ",{}", count);
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Writes the table in Markdown, with a column per number of copies of the pattern.
+    pub fn to_markdown(&self) -> String {
+        let pattern = format_pieces(&self.pattern);
+        let mut markdown = String::from("| board |");
+        for count in &self.counts {
+            let _ = write!(markdown, " {}×{} |", count, pattern);
+        }
+        markdown.push_str("\n|---|");
+        for _ in &self.counts {
+            markdown.push_str("---:|");
+        }
+        markdown.push('\n');
+        for (rows, cols, counts) in &self.rows {
+            let _ = write!(markdown, "| {}x{} |", rows, cols);
+            for count in counts {
+                let _ = write!(markdown, " {} |", count);
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+}
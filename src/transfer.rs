@@ -0,0 +1,163 @@
+//! Transfer-matrix counting for narrow boards.
+//!
+//! Kings and knights only reach two rows away, so a board can be filled one row at a
+//! time while remembering just the last two rows: whether a new row fits depends on
+//! nothing else. The number of ways to reach every combination of last two rows and
+//! pieces placed so far is carried from row to row, which takes time linear in the
+//! number of rows but exponential in the number of columns. This counts boards with a
+//! handful of columns and thousands of rows, far beyond what enumeration can reach.
+
+use crate::bigint::BigUint;
+use crate::ChessPiece;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// The most columns [`count`] handles.
+pub const MAX_COLS: usize = 10;
+
+/// Error returned for problems transfer-matrix counting doesn't handle.
+///
+/// * `Piece` - A piece that reaches further than two rows
+/// * `Columns` - More than [`MAX_COLS`] columns
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Unsupported {
+    Piece(ChessPiece),
+    Columns(usize),
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unsupported::Piece(piece) => write!(
+                f,
+                "{} is not supported by transfer-matrix counting, only K and N are",
+                piece
+            ),
+            Unsupported::Columns(cols) => write!(
+                f,
+                "transfer-matrix counting needs at most {} columns, not {}",
+                MAX_COLS, cols
+            ),
+        }
+    }
+}
+
+impl Error for Unsupported {}
+
+/// The kings and knights of one row, as bit masks over its columns.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+struct Row {
+    kings: u32,
+    knights: u32,
+}
+
+impl Row {
+    fn occupied(self) -> u32 {
+        self.kings | self.knights
+    }
+}
+
+/// Counts the ways to place `pieces`, kings and knights only, on an empty
+/// `rows`×`cols` board so that no piece attacks another one.
+///
+/// The time taken grows linearly with `rows` and exponentially with `cols`; since counts
+/// don't change when a board is transposed, pass the smaller dimension as `cols`.
+///
+/// # Errors
+///
+/// Returns an error if `pieces` holds other pieces than kings and knights, or if `cols`
+/// is greater than [`MAX_COLS`].
+///
+/// # Example
+///
+/// ```
+/// use chess::transfer;
+/// use chess::ChessPiece;
+///
+/// let kings = [ChessPiece::King, ChessPiece::King];
+/// let count = transfer::count(1000, 4, &kings).unwrap();
+/// assert_eq!(count.to_string(), "7985010");
+/// ```
+pub fn count(rows: usize, cols: usize, pieces: &[ChessPiece]) -> Result<BigUint, Unsupported> {
+    if cols > MAX_COLS {
+        return Err(Unsupported::Columns(cols));
+    }
+    let mut kings = 0;
+    let mut knights = 0;
+    for &piece in pieces {
+        match piece {
+            ChessPiece::King => kings += 1,
+            ChessPiece::Knight => knights += 1,
+            other => return Err(Unsupported::Piece(other)),
+        }
+    }
+
+    let full = (1u32 << cols) - 1;
+    let beside = |mask: u32| ((mask << 1) | (mask >> 1)) & full;
+    let two_beside = |mask: u32| ((mask << 2) | (mask >> 2)) & full;
+    let mut patterns = Vec::new();
+    for kings_mask in 0..=full {
+        for knights_mask in 0..=full {
+            let row = Row {
+                kings: kings_mask,
+                knights: knights_mask,
+            };
+            let fits = kings_mask & knights_mask == 0
+                && kings_mask & beside(row.occupied()) == 0
+                && kings_mask.count_ones() as usize <= kings
+                && knights_mask.count_ones() as usize <= knights;
+            if fits {
+                patterns.push(row);
+            }
+        }
+    }
+    // Whether a row may lie one (or two) rows away from another one.
+    let one_apart = |a: Row, b: Row| {
+        a.kings & (b.occupied() | beside(b.occupied())) == 0
+            && b.kings & (a.occupied() | beside(a.occupied())) == 0
+            && a.knights & two_beside(b.occupied()) == 0
+            && b.knights & two_beside(a.occupied()) == 0
+    };
+    let two_apart = |a: Row, b: Row| {
+        a.knights & beside(b.occupied()) == 0 && b.knights & beside(a.occupied()) == 0
+    };
+    let empty = Row {
+        kings: 0,
+        knights: 0,
+    };
+
+    // Ways to reach each (row before last, last row, kings placed, knights placed). Without
+    // knights the row before last doesn't matter and is always left empty.
+    let mut states: HashMap<(Row, Row, usize, usize), BigUint> = HashMap::new();
+    states.insert((empty, empty, 0, 0), BigUint::from(1));
+    for _ in 0..rows {
+        let mut next: HashMap<(Row, Row, usize, usize), BigUint> = HashMap::new();
+        for ((before_last, last, placed_kings, placed_knights), ways) in &states {
+            for &row in &patterns {
+                let new_kings = placed_kings + row.kings.count_ones() as usize;
+                let new_knights = placed_knights + row.knights.count_ones() as usize;
+                if new_kings > kings
+                    || new_knights > knights
+                    || !one_apart(*last, row)
+                    || !two_apart(*before_last, row)
+                {
+                    continue;
+                }
+                let remembered = if knights > 0 { *last } else { empty };
+                *next
+                    .entry((remembered, row, new_kings, new_knights))
+                    .or_default() += ways;
+            }
+        }
+        states = next;
+    }
+
+    let mut total = BigUint::zero();
+    for ((_, _, placed_kings, placed_knights), ways) in &states {
+        if *placed_kings == kings && *placed_knights == knights {
+            total += ways;
+        }
+    }
+    Ok(total)
+}
@@ -0,0 +1,138 @@
+//! Independent checking of solutions.
+//!
+//! The checks here don't go through the solver or [`Board::is_safe`]: they look at a
+//! finished board on its own and compare every pair of pieces with [`Piece::attacks`],
+//! so they can be used to certify the output of any solver.
+
+use crate::{Board, ChessPiece, Piece};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// The reason a board is not a valid solution.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Violation {
+    /// The board has no squares.
+    Dimensions { rows: i8, cols: i8 },
+    /// A piece lies outside the board.
+    OffBoard(Piece),
+    /// Two pieces share a square.
+    SameSquare(Piece, Piece),
+    /// The board doesn't hold the expected number of pieces of a type.
+    Count {
+        piece: ChessPiece,
+        expected: usize,
+        found: usize,
+    },
+    /// A piece attacks another one.
+    Attack { attacker: Piece, victim: Piece },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Dimensions { rows, cols } => {
+                write!(f, "invalid board dimensions {}x{}", rows, cols)
+            }
+            Violation::OffBoard(piece) => write!(f, "{} is off the board", piece),
+            Violation::SameSquare(a, b) => write!(f, "{} and {} share a square", a, b),
+            Violation::Count {
+                piece,
+                expected,
+                found,
+            } => write!(f, "expected {} {} but found {}", expected, piece, found),
+            Violation::Attack { attacker, victim } => {
+                write!(f, "{} attacks {}", attacker, victim)
+            }
+        }
+    }
+}
+
+impl Error for Violation {}
+
+impl Board {
+    /// Checks that the board is a valid answer for the given pieces.
+    ///
+    /// A valid answer has at least one row and one column, holds exactly the pieces of
+    /// `pieces` (in any order), all of them on the board and on different squares, and
+    /// no piece attacks another one.
+    ///
+    /// # Arguments
+    ///
+    /// * `pieces` - The pieces the board should hold
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` for a valid answer, or the first [`Violation`] found
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess::validate::Violation;
+    /// use chess::{Board, ChessPiece, Piece};
+    ///
+    /// let king = Piece { row: 1, col: 1, piece: ChessPiece::King, colour: None };
+    /// let rook = Piece { row: 3, col: 2, piece: ChessPiece::Rook, colour: None };
+    /// let board = Board::new(3, 3, vec![king, rook]);
+    /// assert_eq!(board.validate(&[ChessPiece::Rook, ChessPiece::King]), Ok(()));
+    ///
+    /// let board = Board::new(3, 3, vec![king, Piece { row: 1, col: 3, ..rook }]);
+    /// assert!(matches!(
+    ///     board.validate(&[ChessPiece::King, ChessPiece::Rook]),
+    ///     Err(Violation::Attack { .. })
+    /// ));
+    /// ```
+    pub fn validate(&self, pieces: &[ChessPiece]) -> Result<(), Violation> {
+        if self.rows < 1 || self.cols < 1 {
+            return Err(Violation::Dimensions {
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+        if let Some(&piece) = self.pieces.iter().find(|piece| {
+            !(1..=self.rows).contains(&piece.row) || !(1..=self.cols).contains(&piece.col)
+        }) {
+            return Err(Violation::OffBoard(piece));
+        }
+        for (i, &a) in self.pieces.iter().enumerate() {
+            if let Some(&b) = self.pieces[i + 1..]
+                .iter()
+                .find(|b| (a.row, a.col) == (b.row, b.col))
+            {
+                return Err(Violation::SameSquare(a, b));
+            }
+        }
+        let mut expected: HashMap<ChessPiece, usize> = HashMap::new();
+        for &piece in pieces {
+            *expected.entry(piece).or_insert(0) += 1;
+        }
+        let mut found: HashMap<ChessPiece, usize> = HashMap::new();
+        for piece in &self.pieces {
+            *found.entry(piece.piece).or_insert(0) += 1;
+        }
+        let mut types: Vec<ChessPiece> = expected.keys().chain(found.keys()).copied().collect();
+        types.sort();
+        types.dedup();
+        for piece in types {
+            let expected = expected.get(&piece).copied().unwrap_or(0);
+            let found = found.get(&piece).copied().unwrap_or(0);
+            if expected != found {
+                return Err(Violation::Count {
+                    piece,
+                    expected,
+                    found,
+                });
+            }
+        }
+        for &attacker in &self.pieces {
+            if let Some(&victim) = self
+                .pieces
+                .iter()
+                .find(|&&victim| victim != attacker && attacker.attacks(victim))
+            {
+                return Err(Violation::Attack { attacker, victim });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+// Each test crate uses only some of these.
+#![allow(dead_code)]
+
+use chess::*;
+use std::collections::{HashSet, VecDeque};
+use std::rc::Rc;
+
+/// Returns the board stack [`solution`] starts from to place `pieces` on `board`.
+pub fn board_stack<'a>(
+    board: &Board,
+    pieces: &'a [ChessPiece],
+) -> VecDeque<(Rc<Board>, &'a [ChessPiece])> {
+    let mut board_stack = VecDeque::new();
+    board_stack.push_front((Rc::new(board.clone()), pieces));
+    board_stack
+}
+
+/// Returns the number of solutions [`solution`] finds on an empty `rows`×`cols` board.
+pub fn solution_count(rows: i8, cols: i8, pieces: &[ChessPiece]) -> usize {
+    solutions(&Board::new(rows, cols, Vec::new()), pieces).len()
+}
+
+/// Returns the solutions [`solution`] finds when placing `pieces` on `board`.
+pub fn solutions(board: &Board, pieces: &[ChessPiece]) -> HashSet<Rc<Board>> {
+    let mut solutions = HashSet::new();
+    solution(
+        &mut board_stack(board, pieces),
+        &mut solutions,
+        &mut HashSet::new(),
+    );
+    solutions
+}
@@ -0,0 +1,90 @@
+use chess::rules::{PairRule, Rules};
+use chess::*;
+
+fn all_pairs() -> Rules {
+    Rules {
+        pairs: PairRule::AllPairs,
+        ..Rules::default()
+    }
+}
+
+#[test]
+fn test_peaceable_queens_match_oeis() {
+    // A250000, n = 1..7.
+    let expected = [0, 0, 1, 2, 4, 5, 7];
+    for (n, &size) in (1..).zip(expected.iter()) {
+        assert_eq!(size, armies::peaceable_queens(n).0, "{}x{}", n, n);
+    }
+}
+
+#[test]
+fn test_peaceable_queens_board_is_peaceful() {
+    let (size, board) = armies::peaceable_queens(6);
+    let white = board
+        .pieces
+        .iter()
+        .filter(|piece| piece.colour == Some(Colour::White))
+        .count();
+    let black = board
+        .pieces
+        .iter()
+        .filter(|piece| piece.colour == Some(Colour::Black))
+        .count();
+    assert_eq!((size, size), (white, black));
+    let rules = all_pairs();
+    let mut attacked = 0;
+    for (i, &piece) in board.pieces.iter().enumerate() {
+        let mut others = board.pieces.clone();
+        others.remove(i);
+        let others = Board::new(6, 6, others);
+        assert!(others.is_safe(piece), "{}", piece);
+        attacked += !others.is_safe_with(piece, &rules) as usize;
+    }
+    // Queens of the same colour do attack each other.
+    assert!(attacked > 0);
+}
+
+#[test]
+fn test_pair_rules() {
+    let white = Piece {
+        row: 1,
+        col: 1,
+        piece: ChessPiece::Rook,
+        colour: Some(Colour::White),
+    };
+    let board = Board::new(4, 4, vec![white]);
+    let same_colour = Piece { col: 4, ..white };
+    let other_colour = Piece {
+        colour: Some(Colour::Black),
+        ..same_colour
+    };
+    let colourless = Piece {
+        colour: None,
+        ..same_colour
+    };
+    assert!(board.is_safe(same_colour));
+    assert!(!board.is_safe(other_colour));
+    assert!(!board.is_safe(colourless));
+    // Pieces of the same colour still can't share a square.
+    assert!(!board.is_safe(Piece {
+        col: 1,
+        ..same_colour
+    }));
+    for piece in [same_colour, other_colour, colourless] {
+        assert_eq!(
+            board.is_safe(piece),
+            board.is_safe_with(piece, &Rules::default())
+        );
+        assert!(!board.is_safe_with(piece, &all_pairs()));
+    }
+}
+
+#[test]
+fn test_coloured_notation_round_trip() {
+    let board: Board = "3x3 bQ(3,3) wQ(1,1) K(2,3)".parse().unwrap();
+    assert_eq!(Some(Colour::White), board.pieces[0].colour);
+    assert_eq!(None, board.pieces[1].colour);
+    assert_eq!("3x3 wQ(1,1) K(2,3) bQ(3,3)", board.to_string());
+    assert_eq!(board, board.to_string().parse().unwrap());
+    assert!("xQ(1,1)".parse::<Piece>().is_err());
+}
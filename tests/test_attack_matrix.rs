@@ -0,0 +1,105 @@
+use chess::rules::{AttackMatrix, Rules};
+use chess::*;
+
+fn with_attacks(attacks: AttackMatrix) -> Rules {
+    Rules {
+        attacks,
+        ..Rules::default()
+    }
+}
+
+fn count(rows: i8, cols: i8, pieces: &str, rules: &Rules) -> u64 {
+    let pieces = notation::parse_pieces(pieces).unwrap();
+    search::count_with(&Board::new(rows, cols, Vec::new()), &pieces, rules)
+        .to_u64()
+        .unwrap()
+}
+
+/// Counts the boards holding one `first` and one `second` piece (of different types)
+/// where neither makes a forbidden attack on the other.
+fn brute_force_pair_count(rows: i8, cols: i8, first: &str, second: &str, rules: &Rules) -> u64 {
+    let squares: Vec<(i8, i8)> = (1..=rows)
+        .flat_map(|row| (1..=cols).map(move |col| (row, col)))
+        .collect();
+    let mut total = 0;
+    for &(row, col) in &squares {
+        for &(other_row, other_col) in &squares {
+            let a: Piece = format!("{}({},{})", first, row, col).parse().unwrap();
+            let b: Piece = format!("{}({},{})", second, other_row, other_col)
+                .parse()
+                .unwrap();
+            let forbidden = (row, col) == (other_row, other_col)
+                || (a.attacks(b) && rules.attacks.forbids(a.piece, b.piece))
+                || (b.attacks(a) && rules.attacks.forbids(b.piece, a.piece));
+            total += !forbidden as u64;
+        }
+    }
+    total
+}
+
+#[test]
+fn test_default_matrix_is_symmetric_rule() {
+    let rules = with_attacks(AttackMatrix::default());
+    assert!(rules.forbids_every_attack());
+    assert_eq!(4, count(3, 3, "KKR", &rules));
+    assert_eq!(816, count(5, 5, "KKQQ", &rules));
+    assert_eq!(1224, count(5, 5, "QBR", &rules));
+}
+
+#[test]
+fn test_allowed_attacks() {
+    // Kings may attack each other, so any two squares will do.
+    assert_eq!(36, count(3, 3, "KK", &with_attacks("KK".parse().unwrap())));
+    // Everything allowed: any placement on distinct squares.
+    assert_eq!(
+        36,
+        count(3, 3, "QQ", &with_attacks(AttackMatrix::allow_all()))
+    );
+    // A queen always attacks a king next to it, so letting kings attack queens alone
+    // changes nothing.
+    assert_eq!(
+        count(4, 4, "KQ", &Rules::default()),
+        count(4, 4, "KQ", &with_attacks("KQ".parse().unwrap()))
+    );
+}
+
+#[test]
+fn test_one_directional_rules_match_brute_force() {
+    let mut only_queens_forbidden = AttackMatrix::allow_all();
+    for victim in [ChessPiece::Rook, ChessPiece::Knight, ChessPiece::King] {
+        only_queens_forbidden.forbid(ChessPiece::Queen, victim);
+    }
+    let matrices = [
+        only_queens_forbidden,
+        "K*".parse().unwrap(),
+        "RN,NR".parse().unwrap(),
+        "*Q".parse().unwrap(),
+    ];
+    for matrix in matrices {
+        let rules = with_attacks(matrix);
+        for (first, second) in [("Q", "R"), ("Q", "K"), ("R", "N"), ("K", "Q")] {
+            // The pieces differ in type, so every choice of two squares is its own board.
+            assert_eq!(
+                brute_force_pair_count(4, 5, first, second, &rules),
+                count(4, 5, &format!("{}{}", first, second), &rules),
+                "{}{} with {}",
+                first,
+                second,
+                rules.attacks
+            );
+        }
+    }
+}
+
+#[test]
+fn test_matrix_notation() {
+    let matrix: AttackMatrix = "K*, QN".parse().unwrap();
+    assert!(!matrix.forbids(ChessPiece::King, ChessPiece::Pawn));
+    assert!(!matrix.forbids(ChessPiece::Queen, ChessPiece::Knight));
+    assert!(matrix.forbids(ChessPiece::Knight, ChessPiece::Queen));
+    assert_eq!(matrix, matrix.to_string().parse().unwrap());
+    assert_eq!("", AttackMatrix::default().to_string());
+    assert_eq!(AttackMatrix::allow_all(), "**".parse().unwrap());
+    assert!("KQR".parse::<AttackMatrix>().is_err());
+    assert!("KX".parse::<AttackMatrix>().is_err());
+}
@@ -0,0 +1,133 @@
+use chess::rules::Rules;
+use chess::*;
+
+mod common;
+
+fn k_attacking(k: usize) -> Rules {
+    Rules {
+        max_attackers: k,
+        ..Rules::default()
+    }
+}
+
+/// Counts by trying every way of filling the squares, then checking whole boards.
+fn brute_force_count(rows: i8, cols: i8, pieces: &[ChessPiece], k: usize) -> u64 {
+    fn fill(
+        square: i8,
+        rows: i8,
+        cols: i8,
+        left: &mut Vec<ChessPiece>,
+        placed: &mut Vec<Piece>,
+        k: usize,
+    ) -> u64 {
+        if left.is_empty() {
+            let valid = placed.iter().all(|&victim| {
+                let attackers = placed
+                    .iter()
+                    .filter(|&&piece| piece != victim && piece.attacks(victim))
+                    .count();
+                attackers <= k
+            });
+            return valid as u64;
+        }
+        if square == rows * cols {
+            return 0;
+        }
+        let mut total = fill(square + 1, rows, cols, left, placed, k);
+        let mut types = left.clone();
+        types.dedup();
+        for chess_piece in types {
+            let index = left.iter().position(|&piece| piece == chess_piece).unwrap();
+            left.remove(index);
+            placed.push(Piece {
+                row: square / cols + 1,
+                col: square % cols + 1,
+                piece: chess_piece,
+                colour: None,
+            });
+            total += fill(square + 1, rows, cols, left, placed, k);
+            placed.pop();
+            left.insert(index, chess_piece);
+        }
+        total
+    }
+    let mut left = pieces.to_vec();
+    left.sort();
+    fill(0, rows, cols, &mut left, &mut Vec::new(), k)
+}
+
+#[test]
+fn test_zero_attackers_matches_solution() {
+    let problems: [(i8, i8, &str); 5] = [
+        (3, 3, "KKR"),
+        (4, 4, "QRR"),
+        (5, 5, "KKQQ"),
+        (4, 5, "KQBN"),
+        (4, 4, "PPPK"),
+    ];
+    for (rows, cols, pieces) in problems {
+        let pieces = notation::parse_pieces(pieces).unwrap();
+        let board = Board::new(rows, cols, Vec::new());
+        let expected = common::solution_count(rows, cols, &pieces) as u64;
+        assert_eq!(
+            Some(expected),
+            search::count_with(&board, &pieces, &k_attacking(0)).to_u64()
+        );
+        assert_eq!(
+            search::count(&board, &pieces),
+            search::count_with(&board, &pieces, &Rules::default())
+        );
+    }
+}
+
+#[test]
+fn test_k_attacking_matches_brute_force() {
+    let problems: [(i8, i8, &str); 5] = [
+        (3, 3, "RRR"),
+        (3, 3, "QQQ"),
+        (4, 4, "KKKK"),
+        (4, 4, "QQBN"),
+        (3, 4, "NNRP"),
+    ];
+    for (rows, cols, pieces) in problems {
+        let pieces = notation::parse_pieces(pieces).unwrap();
+        let board = Board::new(rows, cols, Vec::new());
+        for k in 0..=3 {
+            assert_eq!(
+                Some(brute_force_count(rows, cols, &pieces, k)),
+                search::count_with(&board, &pieces, &k_attacking(k)).to_u64(),
+                "{}x{} {:?} k={}",
+                rows,
+                cols,
+                pieces,
+                k
+            );
+        }
+    }
+}
+
+#[test]
+fn test_rooks_in_a_row() {
+    let rooks = [ChessPiece::Rook; 3];
+    let board = Board::new(1, 3, Vec::new());
+    // Attacks go through pieces, so the middle rook has two attackers.
+    assert!(search::count_with(&board, &rooks, &k_attacking(1)).is_zero());
+    assert_eq!(
+        Some(1),
+        search::count_with(&board, &rooks, &k_attacking(2)).to_u64()
+    );
+}
+
+#[test]
+fn test_is_safe_with_attackers() {
+    let board: Board = "3x3 R(1,1) R(1,3)".parse().unwrap();
+    let middle: Piece = "R(1,2)".parse().unwrap();
+    let king: Piece = "K(2,3)".parse().unwrap();
+    assert!(!board.is_safe_with(middle, &k_attacking(1)));
+    assert!(board.is_safe_with(middle, &k_attacking(2)));
+    // R(1,3) already has one attacker, so a king next to it is one too many at k = 1.
+    assert!(!board.is_safe_with(king, &k_attacking(1)));
+    assert!(board.is_safe_with(king, &k_attacking(2)));
+    assert!(board.is_safe_with("K(3,2)".parse().unwrap(), &k_attacking(0)));
+    assert!(!board.is_safe_with("K(1,1)".parse().unwrap(), &k_attacking(3)));
+}
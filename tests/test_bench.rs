@@ -0,0 +1,101 @@
+use chess::bench::{self, Measurement, SOLVERS, SUITE};
+use chess::*;
+use std::time::Duration;
+
+fn measurement(name: &str, solutions: u64, nodes: u64, millis: u64, memory: usize) -> Measurement {
+    Measurement {
+        name: name.to_string(),
+        solutions,
+        nodes,
+        time: Duration::from_millis(millis),
+        memory,
+    }
+}
+
+#[test]
+fn test_measure_small_problems() {
+    for name in ["queens8", "knights6"] {
+        let problem = SUITE.iter().find(|problem| problem.name == name).unwrap();
+        let measurement = problem.measure();
+        let board = Board::new(problem.rows, problem.cols, Vec::new());
+        let pieces = notation::parse_pieces(problem.pieces).unwrap();
+        assert_eq!(name, measurement.name);
+        assert_eq!(
+            search::count(&board, &pieces).to_u64(),
+            Some(measurement.solutions)
+        );
+        assert_eq!(search::nodes(&board, &pieces), measurement.nodes);
+        assert_eq!(0, measurement.memory);
+    }
+}
+
+#[test]
+fn test_solvers_agree() {
+    let problem = SUITE
+        .iter()
+        .find(|problem| problem.name == "queens8")
+        .unwrap();
+    let measurements: Vec<Measurement> = SOLVERS
+        .iter()
+        .map(|&solver| problem.measure_with(solver))
+        .collect();
+    let names: Vec<&str> = measurements.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(vec!["queens8", "queens8/solution", "queens8/dlx"], names);
+    assert!(measurements.iter().all(|m| m.solutions == 92));
+    assert_eq!(problem.measure().nodes, measurements[0].nodes);
+    assert!(measurements[1].nodes > 0);
+    assert_eq!(0, measurements[2].nodes);
+}
+
+#[test]
+fn test_baseline_round_trip() {
+    let measurements = vec![
+        measurement("challenge", 3063828, 4815155, 5124, 10344),
+        measurement("queens8", 92, 11531, 12, 0),
+    ];
+    let mut file = Vec::new();
+    bench::write_baseline(&mut file, &measurements).unwrap();
+    assert_eq!(
+        "# chess bench\nchallenge 3063828 4815155 5.124 10344\nqueens8 92 11531 0.012 0\n",
+        String::from_utf8(file.clone()).unwrap()
+    );
+    assert_eq!(measurements, bench::read_baseline(&file[..]).unwrap());
+    assert!(bench::read_baseline(&b"challenge 1 2 3 4\n"[..]).is_err());
+    assert!(bench::read_baseline(&b"# chess bench\nchallenge 1 2 3\n"[..]).is_err());
+    assert!(bench::read_baseline(&b"# chess bench\nchallenge 1 2 -3 4\n"[..]).is_err());
+}
+
+#[test]
+fn test_report_against_baseline() {
+    let baseline = vec![
+        measurement("challenge", 3063828, 4815155, 5000, 10000),
+        measurement("queens8", 92, 11531, 10, 0),
+    ];
+    let current = vec![
+        measurement("challenge", 3063828, 4815155, 4000, 15000),
+        measurement("queens8", 91, 11531, 10, 0),
+        measurement("knights6", 257318, 354818, 70, 0),
+    ];
+    let report = bench::report(&current, &baseline);
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(4, lines.len());
+    assert!(lines[1].ends_with("nodes =, time -20.0%, memory +50.0%"));
+    assert!(lines[2].ends_with("ERROR: 92 solutions in the baseline"));
+    assert!(lines[3].trim_end().ends_with("KiB"));
+}
+
+#[test]
+fn test_compare_backends() {
+    let measurements = vec![
+        measurement("queens8", 92, 11531, 40, 0),
+        measurement("queens8/solution", 92, 11439, 200, 0),
+        measurement("queens8/dlx", 92, 0, 10, 0),
+        measurement("knights6", 257318, 354818, 100, 0),
+        measurement("challenge/dlx", 3063828, 0, 5000, 0),
+    ];
+    let comparison = bench::compare_backends(&measurements);
+    let lines: Vec<&str> = comparison.lines().collect();
+    assert_eq!(2, lines.len());
+    assert!(lines[1].starts_with("queens8 "));
+    assert!(lines[1].ends_with("0.040s     0.010s      0.25x"));
+}
@@ -0,0 +1,71 @@
+use chess::bigint::BigUint;
+use chess::checkpoint::Checkpoint;
+use chess::*;
+
+#[test]
+fn test_parse_and_display_round_trip() {
+    for digits in [
+        "0",
+        "1",
+        "4294967296",
+        "18446744073709551615",
+        "123456789012345678901234567890123456789",
+    ] {
+        let number: BigUint = digits.parse().unwrap();
+        assert_eq!(digits, number.to_string());
+    }
+    assert_eq!(Ok(BigUint::zero()), "000".parse());
+    assert!("".parse::<BigUint>().is_err());
+    assert!("12a".parse::<BigUint>().is_err());
+    assert!("-1".parse::<BigUint>().is_err());
+}
+
+#[test]
+fn test_arithmetic_and_ordering() {
+    let big: BigUint = "99999999999999999999".parse().unwrap();
+    assert_eq!(
+        "100000000000000000000",
+        (&big + &BigUint::from(1)).to_string()
+    );
+    assert_eq!(
+        "9999999999999999999800000000000000000001",
+        (&big * &big).to_string()
+    );
+    assert!(BigUint::zero().is_zero());
+    assert!((&big * &BigUint::zero()).is_zero());
+    assert!(big > BigUint::from(u64::MAX));
+    assert!(BigUint::from(4294967296) > BigUint::from(4294967295));
+    assert_eq!(None, big.to_u64());
+    let total: BigUint = [BigUint::from(u64::MAX), BigUint::from(u64::MAX)]
+        .iter()
+        .sum();
+    assert_eq!("36893488147419103230", total.to_string());
+}
+
+#[test]
+fn test_checkpoint_keeps_counts_beyond_u64() {
+    let mut checkpoint = Checkpoint::new(Board::new(3, 3, Vec::new()), &[ChessPiece::King]);
+    checkpoint.solutions = "123456789012345678901234567890".parse().unwrap();
+    let mut saved = Vec::new();
+    checkpoint.write(&mut saved).unwrap();
+    assert_eq!(checkpoint, Checkpoint::read(saved.as_slice()).unwrap());
+}
+
+#[test]
+fn test_random_below() {
+    let mut rng = rng::Rng::new(5);
+    // 2^64 + 1 doesn't fit in a u64; about half the draws fall below 2^63.
+    let bound: BigUint = "18446744073709551617".parse().unwrap();
+    let half: BigUint = "9223372036854775808".parse().unwrap();
+    let mut low = 0;
+    for _ in 0..1000 {
+        let number = bound.random_below(&mut rng);
+        assert!(number < bound);
+        low += (number < half) as usize;
+    }
+    assert!((400..=600).contains(&low), "{}", low);
+    assert_eq!(
+        BigUint::from(rng::Rng::new(5).below(1000)),
+        BigUint::from(1000).random_below(&mut rng::Rng::new(5))
+    );
+}
@@ -0,0 +1,84 @@
+use chess::binary::{self, Header, MAGIC};
+use chess::packed::PackedBoard;
+use chess::*;
+use std::io::ErrorKind;
+
+fn solutions(rows: i8, cols: i8, pieces: &[ChessPiece]) -> Vec<PackedBoard> {
+    let mut boards = Vec::new();
+    search::for_each_solution(&Board::new(rows, cols, Vec::new()), pieces, |solution| {
+        boards.push(PackedBoard::try_from(solution).unwrap());
+    });
+    boards
+}
+
+#[test]
+fn test_round_trip() {
+    let pieces = notation::parse_pieces("KKQBNRP").unwrap();
+    let boards = solutions(5, 6, &pieces);
+    let header = Header::new(5, 6, &pieces, boards.len() as u64);
+    let mut file = Vec::new();
+    binary::write(&mut file, &header, &boards).unwrap();
+    assert_eq!(&file[..4], MAGIC);
+    assert_eq!(file.len(), 21 + pieces.len() * boards.len());
+    let (read, read_boards) = binary::read(&file[..]).unwrap();
+    assert_eq!(read, header);
+    assert_eq!(read.pieces, notation::parse_pieces("RBNQKKP").unwrap());
+    assert_eq!(read_boards, boards);
+}
+
+#[test]
+fn test_write_rejects_mismatched_boards() {
+    let pieces = notation::parse_pieces("KKR").unwrap();
+    let boards = solutions(3, 3, &pieces);
+    let short = Header::new(3, 3, &pieces, 3);
+    let err = binary::write(Vec::new(), &short, &boards).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let other = Header::new(3, 3, &notation::parse_pieces("KKN").unwrap(), 4);
+    assert!(binary::write(Vec::new(), &other, &boards).is_err());
+    let larger = Header::new(4, 3, &pieces, 4);
+    assert!(binary::write(Vec::new(), &larger, &boards).is_err());
+}
+
+#[test]
+fn test_read_rejects_bad_headers() {
+    let pieces = notation::parse_pieces("KKR").unwrap();
+    let boards = solutions(3, 3, &pieces);
+    let mut file = Vec::new();
+    binary::write(&mut file, &Header::new(3, 3, &pieces, 4), &boards).unwrap();
+    let mut magic = file.clone();
+    magic[0] = b'X';
+    assert_eq!(
+        binary::read(&magic[..]).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    let mut version = file.clone();
+    version[4] = 2;
+    assert!(binary::read(&version[..]).is_err());
+    let mut empty = file.clone();
+    empty[5] = 0;
+    assert!(binary::read(&empty[..]).is_err());
+    assert!(binary::read(&file[..10]).is_err());
+}
+
+#[test]
+fn test_read_rejects_bad_solutions() {
+    let pieces = notation::parse_pieces("KKR").unwrap();
+    let boards = solutions(3, 3, &pieces);
+    let mut file = Vec::new();
+    binary::write(&mut file, &Header::new(3, 3, &pieces, 4), &boards).unwrap();
+    // Truncated: the last solution is missing a byte.
+    assert!(binary::read(&file[..file.len() - 1]).is_err());
+    // A square off the 3x3 board.
+    let mut off_board = file.clone();
+    off_board[21] = 9;
+    assert!(binary::read(&off_board[..]).is_err());
+    // The kings' squares out of order.
+    let mut unordered = file.clone();
+    unordered.swap(22, 23);
+    assert!(binary::read(&unordered[..]).is_err());
+    // The reader stops at the first bad solution.
+    let mut reader = binary::Reader::new(&off_board[..]).unwrap();
+    assert_eq!(reader.header.count, 4);
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+}
@@ -0,0 +1,126 @@
+use chess::checkpoint::Checkpoint;
+use chess::*;
+
+mod common;
+
+#[test]
+fn test_search_count_matches_solution() {
+    let problems: [(i8, i8, &[ChessPiece]); 4] = [
+        (3, 3, &[ChessPiece::King, ChessPiece::King, ChessPiece::Rook]),
+        (4, 4, &[ChessPiece::Queen, ChessPiece::Rook, ChessPiece::Rook]),
+        (
+            5,
+            5,
+            &[
+                ChessPiece::King,
+                ChessPiece::King,
+                ChessPiece::Bishop,
+                ChessPiece::Bishop,
+            ],
+        ),
+        (4, 5, &[ChessPiece::King, ChessPiece::Queen, ChessPiece::King]),
+    ];
+    for (rows, cols, pieces) in problems {
+        let board = Board::new(rows, cols, Vec::new());
+        assert_eq!(
+            Some(common::solution_count(rows, cols, pieces) as u64),
+            search::count(&board, pieces).to_u64()
+        );
+    }
+}
+
+#[test]
+fn test_search_count_without_pieces() {
+    assert_eq!(
+        Some(1),
+        search::count(&Board::new(2, 2, Vec::new()), &[]).to_u64()
+    );
+}
+
+#[test]
+fn test_resumed_search_matches_uninterrupted_search() {
+    let pieces = [
+        ChessPiece::King,
+        ChessPiece::King,
+        ChessPiece::Queen,
+        ChessPiece::Queen,
+    ];
+    let mut checkpoint = Checkpoint::new(Board::new(5, 5, Vec::new()), &pieces);
+    let mut saves = 0;
+    while !checkpoint.run(3) {
+        let mut saved = Vec::new();
+        checkpoint.write(&mut saved).unwrap();
+        let restored = Checkpoint::read(saved.as_slice()).unwrap();
+        assert_eq!(checkpoint, restored);
+        checkpoint = restored;
+        saves += 1;
+    }
+    assert!(saves > 1);
+    assert_eq!(Some(816), checkpoint.solutions.to_u64());
+}
+
+#[test]
+fn test_checkpoint_save_and_load() {
+    let path = std::env::temp_dir().join(format!("chess-checkpoint-{}", std::process::id()));
+    let pieces = [ChessPiece::Queen, ChessPiece::Bishop, ChessPiece::Rook];
+    let mut checkpoint = Checkpoint::new(Board::new(5, 5, Vec::new()), &pieces);
+    checkpoint.run(10);
+    checkpoint.save(&path).unwrap();
+    let mut restored = Checkpoint::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(checkpoint, restored);
+    restored.run(u64::MAX);
+    assert_eq!(Some(1224), restored.solutions.to_u64());
+}
+
+#[test]
+fn test_read_rejects_other_files() {
+    assert!(Checkpoint::read("7x7 K(1,1)\n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_prepopulated_boards() {
+    // The pieces already on the board may come after the squares of the new ones.
+    let problems = [
+        ("4x4 K(4,4)", "KK", 39),
+        ("5x5 Q(5,5)", "QQQQ", 2),
+        ("4x4 R(4,4)", "RRR", 6),
+        ("5x5 N(3,3) K(5,5)", "KKN", 125),
+    ];
+    for (board, pieces, expected) in problems {
+        let board: Board = board.parse().unwrap();
+        let pieces = notation::parse_pieces(pieces).unwrap();
+        let expected = bigint::BigUint::from(expected);
+        assert_eq!(search::count(&board, &pieces), expected, "{}", board);
+        assert_eq!(
+            search::count_with(&board, &pieces, &rules::Rules::default()),
+            expected
+        );
+        for backend in [
+            backend::Backend::Backtracking,
+            backend::Backend::DancingLinks,
+        ] {
+            assert_eq!(backend.count(&board, &pieces), expected);
+            assert_eq!(
+                backend.solve(&board, &pieces).len(),
+                expected.to_u64().unwrap() as usize
+            );
+        }
+        let mut checkpoint = Checkpoint::new(board.clone(), &pieces);
+        while !checkpoint.run(2) {
+            let mut saved = Vec::new();
+            checkpoint.write(&mut saved).unwrap();
+            checkpoint = Checkpoint::read(saved.as_slice()).unwrap();
+        }
+        assert_eq!(checkpoint.solutions, expected);
+        let shards: bigint::BigUint = (0..3)
+            .map(|index| {
+                let shard = shard::shard(&board, &pieces, index, 3);
+                let mut found = 0u64;
+                shard.for_each_solution(|_| found += 1);
+                bigint::BigUint::from(found)
+            })
+            .sum();
+        assert_eq!(shards, expected);
+    }
+}
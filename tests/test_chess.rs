@@ -8,63 +8,72 @@ fn test_king_movements() {
         row: 5,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let other_piece_1 = Piece {
         row: 5,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_1 = piece_king.attacks(other_piece_1);
-    assert_eq!(attack_1, true);
+    assert!(attack_1);
     let other_piece_2 = Piece {
         row: 6,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_2 = piece_king.attacks(other_piece_2);
-    assert_eq!(attack_2, true);
+    assert!(attack_2);
     let other_piece_3 = Piece {
         row: 5,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_3 = piece_king.attacks(other_piece_3);
-    assert_eq!(attack_3, true);
+    assert!(attack_3);
     let other_piece_4 = Piece {
         row: 4,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_4 = piece_king.attacks(other_piece_4);
-    assert_eq!(attack_4, true);
+    assert!(attack_4);
     let other_piece_5 = Piece {
         row: 4,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_5 = piece_king.attacks(other_piece_5);
-    assert_eq!(attack_5, true);
+    assert!(attack_5);
     let other_piece_6 = Piece {
         row: 4,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_6 = piece_king.attacks(other_piece_6);
-    assert_eq!(attack_6, true);
+    assert!(attack_6);
     let other_piece_7 = Piece {
         row: 6,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_7 = piece_king.attacks(other_piece_7);
-    assert_eq!(attack_7, true);
+    assert!(attack_7);
     let other_piece_8 = Piece {
         row: 8,
         col: 8,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_8 = piece_king.attacks(other_piece_8);
-    assert_eq!(attack_8, false);
+    assert!(!attack_8);
 }
 #[test]
 fn test_bishop_movements() {
@@ -72,71 +81,81 @@ fn test_bishop_movements() {
         row: 5,
         col: 5,
         piece: ChessPiece::Bishop,
+        colour: None,
     };
     let other_piece_1 = Piece {
         row: 7,
         col: 7,
         piece: ChessPiece::Bishop,
+        colour: None,
     };
     let attack_1 = piece_bishop.attacks(other_piece_1);
-    assert_eq!(attack_1, true);
+    assert!(attack_1);
     let other_piece_2 = Piece {
         row: 6,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_2 = piece_bishop.attacks(other_piece_2);
-    assert_eq!(attack_2, true);
+    assert!(attack_2);
     let other_piece_3 = Piece {
         row: 4,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_3 = piece_bishop.attacks(other_piece_3);
-    assert_eq!(attack_3, true);
+    assert!(attack_3);
     let other_piece_4 = Piece {
         row: 3,
         col: 7,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_4 = piece_bishop.attacks(other_piece_4);
-    assert_eq!(attack_4, true);
+    assert!(attack_4);
     let other_piece_5 = Piece {
         row: 7,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_5 = piece_bishop.attacks(other_piece_5);
-    assert_eq!(attack_5, true);
+    assert!(attack_5);
     let other_piece_6 = Piece {
         row: 6,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_6 = piece_bishop.attacks(other_piece_6);
-    assert_eq!(attack_6, true);
+    assert!(attack_6);
     let other_piece_7 = Piece {
         row: 4,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_7 = piece_bishop.attacks(other_piece_7);
-    assert_eq!(attack_7, true);
+    assert!(attack_7);
 
     let other_piece_8 = Piece {
         row: 3,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_8 = piece_bishop.attacks(other_piece_8);
-    assert_eq!(attack_8, true);
+    assert!(attack_8);
     let other_piece_9 = Piece {
         row: 8,
         col: 1,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_9 = piece_bishop.attacks(other_piece_9);
-    assert_eq!(attack_9, false);
+    assert!(!attack_9);
 }
 #[test]
 fn test_knight_movements() {
@@ -144,64 +163,73 @@ fn test_knight_movements() {
         row: 5,
         col: 5,
         piece: ChessPiece::Knight,
+        colour: None,
     };
 
     let other_piece_1 = Piece {
         row: 7,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_1 = piece_knight.attacks(other_piece_1);
-    assert_eq!(attack_1, true);
+    assert!(attack_1);
     let other_piece_2 = Piece {
         row: 6,
         col: 7,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_2 = piece_knight.attacks(other_piece_2);
-    assert_eq!(attack_2, true);
+    assert!(attack_2);
     let other_piece_3 = Piece {
         row: 4,
         col: 7,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_3 = piece_knight.attacks(other_piece_3);
-    assert_eq!(attack_3, true);
+    assert!(attack_3);
     let other_piece_4 = Piece {
         row: 3,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_4 = piece_knight.attacks(other_piece_4);
-    assert_eq!(attack_4, true);
+    assert!(attack_4);
     let other_piece_5 = Piece {
         row: 3,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_5 = piece_knight.attacks(other_piece_5);
-    assert_eq!(attack_5, true);
+    assert!(attack_5);
     let other_piece_6 = Piece {
         row: 4,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_6 = piece_knight.attacks(other_piece_6);
-    assert_eq!(attack_6, true);
+    assert!(attack_6);
     let other_piece_7 = Piece {
         row: 6,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_7 = piece_knight.attacks(other_piece_7);
-    assert_eq!(attack_7, true);
+    assert!(attack_7);
     let other_piece_9 = Piece {
         row: 8,
         col: 1,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_9 = piece_knight.attacks(other_piece_9);
-    assert_eq!(attack_9, false);
+    assert!(!attack_9);
 }
 #[test]
 fn test_rook_movements() {
@@ -209,71 +237,81 @@ fn test_rook_movements() {
         row: 5,
         col: 5,
         piece: ChessPiece::Rook,
+        colour: None,
     };
 
     let other_piece_1 = Piece {
         row: 6,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_1 = piece_rook.attacks(other_piece_1);
-    assert_eq!(attack_1, true);
+    assert!(attack_1);
     let other_piece_2 = Piece {
         row: 7,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_2 = piece_rook.attacks(other_piece_2);
-    assert_eq!(attack_2, true);
+    assert!(attack_2);
     let other_piece_3 = Piece {
         row: 4,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_3 = piece_rook.attacks(other_piece_3);
-    assert_eq!(attack_3, true);
+    assert!(attack_3);
     let other_piece_4 = Piece {
         row: 3,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_4 = piece_rook.attacks(other_piece_4);
-    assert_eq!(attack_4, true);
+    assert!(attack_4);
     let other_piece_5 = Piece {
         row: 5,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_5 = piece_rook.attacks(other_piece_5);
-    assert_eq!(attack_5, true);
+    assert!(attack_5);
     let other_piece_6 = Piece {
         row: 5,
         col: 7,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_6 = piece_rook.attacks(other_piece_6);
-    assert_eq!(attack_6, true);
+    assert!(attack_6);
     let other_piece_7 = Piece {
         row: 5,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_7 = piece_rook.attacks(other_piece_7);
-    assert_eq!(attack_7, true);
+    assert!(attack_7);
     let other_piece_9 = Piece {
         row: 5,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_9 = piece_rook.attacks(other_piece_9);
-    assert_eq!(attack_9, true);
+    assert!(attack_9);
     let other_piece_10 = Piece {
         row: 7,
         col: 2,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_10 = piece_rook.attacks(other_piece_10);
-    assert_eq!(attack_10, false);
+    assert!(!attack_10);
 }
 #[test]
 fn test_queen_movements() {
@@ -281,127 +319,145 @@ fn test_queen_movements() {
         row: 5,
         col: 5,
         piece: ChessPiece::Queen,
+        colour: None,
     };
 
     let other_piece_1 = Piece {
         row: 7,
         col: 7,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_1 = piece_queen.attacks(other_piece_1);
-    assert_eq!(attack_1, true);
+    assert!(attack_1);
     let other_piece_2 = Piece {
         row: 6,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_2 = piece_queen.attacks(other_piece_2);
-    assert_eq!(attack_2, true);
+    assert!(attack_2);
     let other_piece_3 = Piece {
         row: 4,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_3 = piece_queen.attacks(other_piece_3);
-    assert_eq!(attack_3, true);
+    assert!(attack_3);
     let other_piece_4 = Piece {
         row: 3,
         col: 7,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_4 = piece_queen.attacks(other_piece_4);
-    assert_eq!(attack_4, true);
+    assert!(attack_4);
     let other_piece_5 = Piece {
         row: 7,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_5 = piece_queen.attacks(other_piece_5);
-    assert_eq!(attack_5, true);
+    assert!(attack_5);
     let other_piece_6 = Piece {
         row: 6,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_6 = piece_queen.attacks(other_piece_6);
-    assert_eq!(attack_6, true);
+    assert!(attack_6);
     let other_piece_7 = Piece {
         row: 4,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_7 = piece_queen.attacks(other_piece_7);
-    assert_eq!(attack_7, true);
+    assert!(attack_7);
     let other_piece_9 = Piece {
         row: 3,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_9 = piece_queen.attacks(other_piece_9);
-    assert_eq!(attack_9, true);
+    assert!(attack_9);
     let other_piece_10 = Piece {
         row: 6,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_10 = piece_queen.attacks(other_piece_10);
-    assert_eq!(attack_10, true);
+    assert!(attack_10);
     let other_piece_11 = Piece {
         row: 7,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_11 = piece_queen.attacks(other_piece_11);
-    assert_eq!(attack_11, true);
+    assert!(attack_11);
     let other_piece_12 = Piece {
         row: 4,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_12 = piece_queen.attacks(other_piece_12);
-    assert_eq!(attack_12, true);
+    assert!(attack_12);
     let other_piece_13 = Piece {
         row: 3,
         col: 5,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_13 = piece_queen.attacks(other_piece_13);
-    assert_eq!(attack_13, true);
+    assert!(attack_13);
     let other_piece_14 = Piece {
         row: 5,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_14 = piece_queen.attacks(other_piece_14);
-    assert_eq!(attack_14, true);
+    assert!(attack_14);
     let other_piece_15 = Piece {
         row: 5,
         col: 7,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_15 = piece_queen.attacks(other_piece_15);
-    assert_eq!(attack_15, true);
+    assert!(attack_15);
     let other_piece_16 = Piece {
         row: 5,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_16 = piece_queen.attacks(other_piece_16);
-    assert_eq!(attack_16, true);
+    assert!(attack_16);
     let other_piece_17 = Piece {
         row: 5,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_17 = piece_queen.attacks(other_piece_17);
-    assert_eq!(attack_17, true);
+    assert!(attack_17);
     let other_piece_18 = Piece {
         row: 3,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let attack_18 = piece_queen.attacks(other_piece_18);
-    assert_eq!(attack_18, false);
+    assert!(!attack_18);
 }
 
 #[test]
@@ -410,21 +466,25 @@ fn test_same_board() {
         row: 5,
         col: 6,
         piece: ChessPiece::King,
+        colour: None,
     };
     let piece_2 = Piece {
         row: 3,
         col: 4,
         piece: ChessPiece::King,
+        colour: None,
     };
     let piece_3 = Piece {
         row: 3,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let piece_4 = Piece {
         row: 3,
         col: 3,
         piece: ChessPiece::King,
+        colour: None,
     };
     let board_a = Board {
         rows: 3,
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn chess(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_chess"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("chess-cli-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn test_flags_do_not_consume_files() {
+    let text = temp_path("queens.txt");
+    let binary = temp_path("queens.bin");
+    let problem = ["--rows", "4", "--cols", "4", "--pieces", "QQQQ"];
+    let dumped = chess(&[&["dump", "--output", text.to_str().unwrap()], &problem[..]].concat());
+    assert!(dumped.status.success(), "{}", stderr(&dumped));
+    let dumped = chess(
+        &[
+            &["dump", "--binary", "--output", binary.to_str().unwrap()],
+            &problem[..],
+        ]
+        .concat(),
+    );
+    assert!(dumped.status.success(), "{}", stderr(&dumped));
+
+    let read = chess(&["read", "--count", binary.to_str().unwrap()]);
+    assert!(read.status.success(), "{}", stderr(&read));
+    assert!(stdout(&read).contains("Size 2"));
+    let query = chess(&["query", "--count", text.to_str().unwrap()]);
+    assert!(query.status.success(), "{}", stderr(&query));
+    assert_eq!(stdout(&query).trim(), "Size 2");
+
+    std::fs::remove_file(&text).unwrap();
+    std::fs::remove_file(&binary).unwrap();
+}
+
+#[test]
+fn test_unknown_options_are_rejected() {
+    let output = chess(&["count", "--rws", "3"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("unknown option `--rws`"));
+    assert!(stderr(&output).contains("usage:"));
+}
+
+#[test]
+fn test_missing_values_are_rejected() {
+    let output = chess(&["count", "--rows", "--cols", "3"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("missing value for --rows"));
+}
+
+#[test]
+fn test_shard_solutions_reject_checkpoints() {
+    let checkpoint = temp_path("shard.checkpoint");
+    let output = chess(&[
+        "shard",
+        "--index",
+        "0",
+        "--of",
+        "2",
+        "--solutions",
+        "--checkpoint",
+        checkpoint.to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("--checkpoint"));
+    assert!(!checkpoint.exists());
+}
+
+#[test]
+fn test_sweep_rejects_reversed_ranges() {
+    for (option, range) in [("--rows", "4..2"), ("--counts", "3..1")] {
+        let output = chess(&["sweep", "--pieces", "K", option, range]);
+        assert!(!output.status.success());
+        assert!(stderr(&output).contains(&format!("invalid range `{}`", range)));
+        assert!(stdout(&output).is_empty());
+    }
+    let output = chess(&["sweep", "--pieces", "K", "--rows", "2..2", "--cols", "2"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+}
@@ -0,0 +1,108 @@
+use chess::*;
+
+mod common;
+
+/// Counts the models of `cnf` by trying every assignment of the placement variables,
+/// the auxiliary variables being fixed by them.
+fn count_models(cnf: &dimacs::Cnf) -> usize {
+    let placements: Vec<Piece> = cnf
+        .types
+        .iter()
+        .flat_map(|&piece| {
+            (1..=cnf.rows).flat_map(move |row| {
+                (1..=cnf.cols).map(move |col| Piece {
+                    row,
+                    col,
+                    piece,
+                    colour: None,
+                })
+            })
+        })
+        .collect();
+    (0u32..1 << placements.len())
+        .filter(|assignment| {
+            let pieces = placements
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| assignment & (1 << i) != 0)
+                .map(|(_, &piece)| piece)
+                .collect();
+            cnf.is_satisfied(&cnf.model(&Board::new(cnf.rows, cnf.cols, pieces)))
+        })
+        .count()
+}
+
+#[test]
+fn test_solutions_round_trip() {
+    let pieces = [ChessPiece::Queen, ChessPiece::Queen, ChessPiece::Bishop];
+    let cnf = dimacs::encode(4, 4, &pieces);
+    let solutions = common::solutions(&Board::new(4, 4, Vec::new()), &pieces);
+    assert_eq!(72, solutions.len());
+    for board in &solutions {
+        let model = cnf.model(board);
+        assert!(cnf.is_satisfied(&model));
+        assert_eq!(**board, cnf.decode(&model));
+    }
+}
+
+#[test]
+fn test_non_solutions_are_rejected() {
+    let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+    let cnf = dimacs::encode(3, 3, &pieces);
+    for line in [
+        "3x3 K(1,1) K(1,2) R(3,3)",
+        "3x3 K(1,1) R(3,2)",
+        "3x3 K(1,1) K(1,3) K(3,1) R(3,2)",
+    ] {
+        let board: Board = line.parse().unwrap();
+        assert!(!cnf.is_satisfied(&cnf.model(&board)), "{}", line);
+    }
+}
+
+#[test]
+fn test_model_count_matches_solution() {
+    let problems: [(i8, i8, &[ChessPiece]); 3] = [
+        (
+            3,
+            3,
+            &[ChessPiece::King, ChessPiece::King, ChessPiece::Rook],
+        ),
+        (
+            2,
+            4,
+            &[ChessPiece::Queen, ChessPiece::Knight, ChessPiece::Knight],
+        ),
+        (
+            3,
+            3,
+            &[ChessPiece::Bishop, ChessPiece::Bishop, ChessPiece::Bishop],
+        ),
+    ];
+    for (rows, cols, pieces) in problems {
+        let cnf = dimacs::encode(rows, cols, pieces);
+        assert_eq!(
+            common::solution_count(rows, cols, pieces),
+            count_models(&cnf)
+        );
+    }
+}
+
+#[test]
+fn test_decode_solver_output() {
+    let cnf = dimacs::encode(
+        3,
+        3,
+        &[ChessPiece::King, ChessPiece::King, ChessPiece::Rook],
+    );
+    let k11 = Piece {
+        row: 1,
+        col: 1,
+        piece: ChessPiece::Rook,
+        colour: None,
+    };
+    let rook = cnf.variable(k11).unwrap();
+    let output = format!("c comment\ns SATISFIABLE\nv -1 -2 {}\nv -40 0\n", rook);
+    let model = dimacs::parse_model(&output);
+    assert_eq!(vec![-1, -2, rook, -40], model);
+    assert_eq!(Board::new(3, 3, vec![k11]), cnf.decode(&model));
+}
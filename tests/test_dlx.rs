@@ -0,0 +1,65 @@
+use chess::backend::Backend;
+use chess::*;
+
+fn dlx_count(rows: i8, cols: i8, pieces: &str) -> u64 {
+    let pieces = notation::parse_pieces(pieces).unwrap();
+    dlx::count(&Board::new(rows, cols, Vec::new()), &pieces)
+        .to_u64()
+        .unwrap()
+}
+
+#[test]
+fn test_dlx_matches_test_chess_counts() {
+    assert_eq!(4, dlx_count(3, 3, "KKR"));
+    assert_eq!(20, dlx_count(4, 4, "KKQQ"));
+    assert_eq!(116, dlx_count(4, 4, "QRR"));
+    assert_eq!(72, dlx_count(4, 4, "QQB"));
+    assert_eq!(816, dlx_count(5, 5, "KKQQ"));
+    assert_eq!(1152, dlx_count(5, 5, "QBB"));
+    assert_eq!(1224, dlx_count(5, 5, "QBR"));
+    assert_eq!(7596, dlx_count(5, 5, "KKBB"));
+    assert_eq!(92, dlx_count(8, 8, "QQQQQQQQ"));
+}
+
+#[test]
+fn test_dlx_board_7x7_2_k_2_q_2_b_1_k() {
+    assert_eq!(3_063_828, dlx_count(7, 7, "KKQQBBN"));
+}
+
+#[test]
+fn test_dlx_finds_same_solutions() {
+    for (rows, cols, pieces) in [
+        (4, 5, "KQN"),
+        (5, 4, "RNN"),
+        (4, 4, "KBBN"),
+        (3, 6, "QK"),
+        (4, 5, "RRRR"),
+        (4, 6, "RRRRN"),
+        (5, 5, "QQQQQ"),
+        (4, 4, "RRRRQ"),
+    ] {
+        let board = Board::new(rows, cols, Vec::new());
+        let pieces = notation::parse_pieces(pieces).unwrap();
+        assert_eq!(
+            Backend::Backtracking.solve(&board, &pieces),
+            Backend::DancingLinks.solve(&board, &pieces)
+        );
+    }
+}
+
+#[test]
+fn test_dlx_keeps_pieces_already_placed() {
+    let board: Board = "5x5 Q(1,1)".parse().unwrap();
+    let pieces = notation::parse_pieces("QQQQ").unwrap();
+    assert_eq!(
+        Backend::Backtracking.solve(&board, &pieces),
+        Backend::DancingLinks.solve(&board, &pieces)
+    );
+    assert_eq!(Some(2), dlx::count(&board, &pieces).to_u64());
+}
+
+#[test]
+fn test_dlx_without_pieces() {
+    assert_eq!(1, dlx_count(2, 2, ""));
+    assert_eq!(0, dlx_count(2, 2, "QQ"));
+}
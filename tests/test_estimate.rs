@@ -0,0 +1,41 @@
+use chess::*;
+
+#[test]
+fn test_estimate_covers_exact_count() {
+    let pieces = [
+        ChessPiece::King,
+        ChessPiece::King,
+        ChessPiece::Queen,
+        ChessPiece::Queen,
+    ];
+    let estimate = estimate::estimate(&Board::new(5, 5, Vec::new()), &pieces, 20_000, 11);
+    assert_eq!(20_000, estimate.probes);
+    assert!(estimate.lower <= 816.0 && 816.0 <= estimate.upper);
+    assert!((estimate.count - 816.0).abs() < 0.1 * 816.0);
+}
+
+#[test]
+fn test_estimate_is_reproducible() {
+    let pieces = [ChessPiece::Queen, ChessPiece::Bishop, ChessPiece::Rook];
+    let board = Board::new(5, 5, Vec::new());
+    assert_eq!(
+        estimate::estimate(&board, &pieces, 100, 5),
+        estimate::estimate(&board, &pieces, 100, 5)
+    );
+}
+
+#[test]
+fn test_estimate_without_solutions() {
+    let pieces = [ChessPiece::Queen, ChessPiece::Queen];
+    let estimate = estimate::estimate(&Board::new(2, 2, Vec::new()), &pieces, 50, 0);
+    assert_eq!(0.0, estimate.count);
+    assert_eq!(0.0, estimate.upper);
+}
+
+#[test]
+fn test_estimate_large_board() {
+    let pieces = notation::parse_pieces("3K3Q2B2R2N").unwrap();
+    let estimate = estimate::estimate(&Board::new(12, 12, Vec::new()), &pieces, 200, 3);
+    assert!(estimate.count > 0.0);
+    assert!(estimate.lower < estimate.count && estimate.count < estimate.upper);
+}
@@ -0,0 +1,79 @@
+use chess::heatmap::Heatmap;
+use chess::notation::parse_pieces;
+use chess::*;
+
+#[test]
+fn test_counts_add_up() {
+    let board = Board::new(5, 6, Vec::new());
+    let pieces = parse_pieces("KKQBN").unwrap();
+    let heatmap = Heatmap::of(&board, &pieces);
+    assert_eq!(
+        search::count(&board, &pieces).to_u64(),
+        Some(heatmap.solutions)
+    );
+    assert_eq!(
+        vec![
+            ChessPiece::Bishop,
+            ChessPiece::Knight,
+            ChessPiece::Queen,
+            ChessPiece::King
+        ],
+        heatmap.types()
+    );
+    for chess_piece in heatmap.types() {
+        let per_solution = pieces.iter().filter(|&&piece| piece == chess_piece).count() as u64;
+        let grid = heatmap.grid(chess_piece);
+        assert_eq!(5, grid.len());
+        assert!(grid.iter().all(|row| row.len() == 6));
+        assert_eq!(
+            per_solution * heatmap.solutions,
+            grid.iter().flatten().sum::<u64>()
+        );
+    }
+}
+
+#[test]
+fn test_queens_are_symmetric() {
+    let heatmap = Heatmap::of(&Board::new(8, 8, Vec::new()), &[ChessPiece::Queen; 8]);
+    assert_eq!(92, heatmap.solutions);
+    for row in 1..=8 {
+        for col in 1..=8 {
+            let count = heatmap.count(ChessPiece::Queen, row, col);
+            assert_eq!(count, heatmap.count(ChessPiece::Queen, 9 - row, col));
+            assert_eq!(count, heatmap.count(ChessPiece::Queen, col, row));
+        }
+        // One queen per row in every solution.
+        assert_eq!(
+            92,
+            heatmap.grid(ChessPiece::Queen)[row as usize - 1]
+                .iter()
+                .sum::<u64>()
+        );
+    }
+    assert_eq!(4, heatmap.count(ChessPiece::Queen, 1, 1));
+}
+
+#[test]
+fn test_heatmap_of_saved_solutions() {
+    let board = Board::new(4, 4, Vec::new());
+    let pieces = parse_pieces("RRN").unwrap();
+    let mut heatmap = Heatmap::new(4, 4);
+    for solution in backend::Backend::Backtracking.solve(&board, &pieces) {
+        heatmap.add(&solution);
+    }
+    assert_eq!(Heatmap::of(&board, &pieces), heatmap);
+}
+
+#[test]
+fn test_renderings() {
+    let heatmap = Heatmap::of(&Board::new(3, 3, Vec::new()), &parse_pieces("KKR").unwrap());
+    assert_eq!(
+        "3x3 4 solutions\n\nR (@ = 1)\n @ \n@ @\n @ \n\nK (@ = 2)\n@ @\n   \n@ @",
+        heatmap.to_string()
+    );
+    let svg = heatmap.to_svg();
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(18, svg.matches("<rect ").count());
+    assert!(svg.contains("<title>K(1,1): 2</title>"));
+}
@@ -0,0 +1,43 @@
+use chess::notation::{parse_dimensions, parse_pieces, MAX_PIECES};
+use chess::*;
+
+#[test]
+fn test_piece_counts() {
+    assert_eq!(parse_pieces("12N").unwrap().len(), 12);
+    assert_eq!(
+        parse_pieces(&format!("{}K", MAX_PIECES)).unwrap().len(),
+        MAX_PIECES
+    );
+    for too_many in ["99999999999999999999K", "9999999999K", "65536Q", "65535KN"] {
+        assert!(parse_pieces(too_many).is_err(), "{}", too_many);
+    }
+    assert!(parse_pieces("2K3").is_err());
+}
+
+#[test]
+fn test_dimensions() {
+    assert_eq!(parse_dimensions("7x9"), Ok((7, 9)));
+    assert_eq!(parse_dimensions("63x63"), Ok((MAX_SIZE, MAX_SIZE)));
+    for invalid in ["0x7", "7x64", "127x127", "7", "7x-1"] {
+        assert!(parse_dimensions(invalid).is_err(), "{}", invalid);
+        assert!(format!("{} K(1,1)", invalid).parse::<Board>().is_err());
+    }
+}
+
+#[test]
+fn test_attacks_at_extreme_coordinates() {
+    let piece = |piece, row, col| Piece {
+        row,
+        col,
+        piece,
+        colour: None,
+    };
+    let corner = piece(ChessPiece::Queen, i8::MAX, i8::MAX);
+    let far = piece(ChessPiece::Bishop, i8::MIN, i8::MIN);
+    assert!(corner.attacks(far));
+    assert!(!piece(ChessPiece::Bishop, i8::MIN, i8::MAX).attacks(corner));
+    for chess_piece in [ChessPiece::King, ChessPiece::Knight, ChessPiece::Pawn] {
+        assert!(!piece(chess_piece, i8::MAX, i8::MAX).attacks(far));
+        assert!(!piece(chess_piece, i8::MIN, i8::MIN).attacks(corner));
+    }
+}
@@ -0,0 +1,51 @@
+use chess::bigint::BigUint;
+use chess::oeis::{self, Mismatch};
+
+/// Sequences placing two pieces, whatever the board size.
+const PAIRS: [&str; 4] = ["A036464", "A061995", "A172132", "A172123"];
+
+#[test]
+fn test_first_terms() {
+    // Boards up to 6x6 keep the n-piece sequences quick.
+    for sequence in oeis::SEQUENCES {
+        let terms = 7 - sequence.first;
+        assert_eq!(Ok(()), sequence.check(terms), "{}", sequence.name);
+    }
+}
+
+#[test]
+fn test_pair_sequences() {
+    for id in PAIRS {
+        let sequence = oeis::find(id).unwrap();
+        assert_eq!(10, sequence.terms.len());
+        assert_eq!(Ok(()), sequence.check(sequence.terms.len()));
+    }
+    assert_eq!(Ok(()), oeis::find("A000170").unwrap().check(8));
+}
+
+#[test]
+fn test_sequence_lookup() {
+    let kings = oeis::find("A201862").unwrap();
+    assert_eq!(Some(79), kings.term(4));
+    assert_eq!(None, kings.term(0));
+    assert_eq!(None, kings.term(100));
+    assert_eq!(BigUint::from(8), kings.count(3));
+    let bishops = oeis::find("A000079").unwrap();
+    assert_eq!(Some(4), bishops.term(2));
+    assert!(oeis::find("A000001").is_none());
+    let mut ids: Vec<&str> = oeis::SEQUENCES.iter().map(|sequence| sequence.id).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(oeis::SEQUENCES.len(), ids.len());
+}
+
+#[test]
+fn test_mismatch_message() {
+    let mismatch = Mismatch {
+        id: "A000170",
+        n: 8,
+        expected: 92,
+        found: BigUint::from(91),
+    };
+    assert_eq!("A000170(8): expected 92 but found 91", mismatch.to_string());
+}
@@ -0,0 +1,149 @@
+use chess::backend::Backend;
+use chess::rng::Rng;
+use chess::*;
+use std::collections::HashSet;
+
+mod common;
+
+const TYPES: [ChessPiece; 6] = [
+    ChessPiece::Rook,
+    ChessPiece::Bishop,
+    ChessPiece::Knight,
+    ChessPiece::Queen,
+    ChessPiece::King,
+    ChessPiece::Pawn,
+];
+
+fn solutions(board: &Board, pieces: &[ChessPiece]) -> HashSet<Board> {
+    common::solutions(board, pieces)
+        .iter()
+        .map(|board| (**board).clone())
+        .collect()
+}
+
+/// Draws a board of up to 4x5 squares and one to four pieces, pawns only if `pawns`.
+fn random_problem(rng: &mut Rng, pawns: bool) -> (Board, Vec<ChessPiece>) {
+    let rows = rng.below(4) as i8 + 1;
+    let cols = rng.below(5) as i8 + 1;
+    let types = if pawns { TYPES.len() } else { TYPES.len() - 1 };
+    let pieces = (0..rng.below(4) + 1)
+        .map(|_| TYPES[rng.below(types as u64) as usize])
+        .collect();
+    (Board::new(rows, cols, Vec::new()), pieces)
+}
+
+/// Maps `(row, col)` to `(col, row)`.
+fn transpose(board: &Board) -> Board {
+    let mut pieces: Vec<Piece> = board
+        .pieces
+        .iter()
+        .map(|&piece| Piece {
+            row: piece.col,
+            col: piece.row,
+            ..piece
+        })
+        .collect();
+    pieces.sort();
+    Board::new(board.cols, board.rows, pieces)
+}
+
+/// Maps column `col` to `cols + 1 - col`.
+fn mirror(board: &Board) -> Board {
+    let mut pieces: Vec<Piece> = board
+        .pieces
+        .iter()
+        .map(|&piece| Piece {
+            col: board.cols + 1 - piece.col,
+            ..piece
+        })
+        .collect();
+    pieces.sort();
+    Board::new(board.rows, board.cols, pieces)
+}
+
+#[test]
+fn test_oracle_known_counts() {
+    let problems: [(i8, i8, &str, usize); 5] = [
+        (3, 3, "KKR", 4),
+        (4, 4, "RRNNNN", 8),
+        (4, 4, "QQQQ", 2),
+        (8, 8, "QQ", 1288),
+        (3, 3, "", 1),
+    ];
+    for (rows, cols, pieces, expected) in problems {
+        let pieces = notation::parse_pieces(pieces).unwrap();
+        assert_eq!(
+            expected,
+            oracle::count(&Board::new(rows, cols, Vec::new()), &pieces)
+        );
+    }
+    // Pieces already on the board stay put and must not be attacked either.
+    let board: Board = "3x3 R(1,1)".parse().unwrap();
+    let expected: HashSet<Board> = [
+        "3x3 R(1,1) R(2,2)",
+        "3x3 R(1,1) R(2,3)",
+        "3x3 R(1,1) R(3,2)",
+        "3x3 R(1,1) R(3,3)",
+    ]
+    .iter()
+    .map(|board| board.parse().unwrap())
+    .collect();
+    assert_eq!(expected, oracle::solutions(&board, &[ChessPiece::Rook]));
+}
+
+#[test]
+fn test_random_problems_match_oracle() {
+    let mut rng = Rng::new(46);
+    for _ in 0..200 {
+        let (board, pieces) = random_problem(&mut rng, true);
+        let expected = oracle::solutions(&board, &pieces);
+        let problem = format!("{} {}", board, notation::format_pieces(&pieces));
+        assert_eq!(expected, solutions(&board, &pieces), "{}", problem);
+        assert_eq!(
+            Some(expected.len() as u64),
+            search::count(&board, &pieces).to_u64(),
+            "{}",
+            problem
+        );
+        assert_eq!(
+            Some(expected.len() as u64),
+            Backend::DancingLinks.count(&board, &pieces).to_u64(),
+            "{}",
+            problem
+        );
+    }
+}
+
+#[test]
+fn test_transposing_preserves_solutions() {
+    let mut rng = Rng::new(7);
+    for _ in 0..100 {
+        // Pawns attack towards row 1, which transposing turns into column 1.
+        let (board, pieces) = random_problem(&mut rng, false);
+        let transposed: HashSet<Board> = solutions(&board, &pieces).iter().map(transpose).collect();
+        assert_eq!(
+            solutions(&transpose(&board), &pieces),
+            transposed,
+            "{} {}",
+            board,
+            notation::format_pieces(&pieces)
+        );
+    }
+}
+
+#[test]
+fn test_mirroring_preserves_solutions() {
+    let mut rng = Rng::new(11);
+    for _ in 0..100 {
+        // Mirroring keeps rows in place, so pawns still attack towards row 1.
+        let (board, pieces) = random_problem(&mut rng, true);
+        let mirrored: HashSet<Board> = solutions(&board, &pieces).iter().map(mirror).collect();
+        assert_eq!(
+            solutions(&board, &pieces),
+            mirrored,
+            "{} {}",
+            board,
+            notation::format_pieces(&pieces)
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use chess::notation::{format_pieces, parse_pieces};
+use chess::order::PieceOrder;
+use chess::*;
+
+#[test]
+fn test_orders_find_the_same_solutions() {
+    let problems: [(i8, i8, &str); 3] = [(5, 5, "KKQB"), (4, 5, "NNRQ"), (5, 6, "KKQBBN")];
+    for (rows, cols, pieces) in problems {
+        let board = Board::new(rows, cols, Vec::new());
+        let pieces = parse_pieces(pieces).unwrap();
+        let expected = search::count(&board, &pieces);
+        for order in PieceOrder::ALL {
+            for seed in 0..3 {
+                let arranged = order.arrange(&board, &pieces, seed);
+                assert_eq!(expected, search::count(&board, &arranged), "{}", order);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_most_constraining_first() {
+    let board = Board::new(5, 5, Vec::new());
+    let pieces = parse_pieces("NNNRQ").unwrap();
+    let arranged = PieceOrder::MostConstraining.arrange(&board, &pieces, 0);
+    assert_eq!("QRNNN", format_pieces(&arranged));
+    assert!(search::nodes(&board, &arranged) < search::nodes(&board, &pieces));
+    // On a single row a rook reaches as far as a queen, and comes first by type.
+    let row = Board::new(1, 6, Vec::new());
+    let arranged = PieceOrder::MostConstraining.arrange(&row, &parse_pieces("KQR").unwrap(), 0);
+    assert_eq!("RQK", format_pieces(&arranged));
+}
+
+#[test]
+fn test_random_order() {
+    let board = Board::new(7, 7, Vec::new());
+    let pieces = parse_pieces("KKQQBBN").unwrap();
+    let arranged = PieceOrder::Random.arrange(&board, &pieces, 7);
+    assert_eq!(arranged, PieceOrder::Random.arrange(&board, &pieces, 7));
+    let mut sorted = arranged.clone();
+    sorted.sort();
+    let mut expected = pieces.clone();
+    expected.sort();
+    assert_eq!(expected, sorted);
+    assert!((0..10).any(|seed| PieceOrder::Random.arrange(&board, &pieces, seed) != arranged));
+}
+
+#[test]
+fn test_order_notation() {
+    for order in PieceOrder::ALL {
+        assert_eq!(order, order.to_string().parse().unwrap());
+    }
+    assert!("fastest".parse::<PieceOrder>().is_err());
+}
@@ -0,0 +1,78 @@
+use chess::packed::{PackError, PackedBoard, CAPACITY};
+use chess::*;
+use std::collections::HashSet;
+
+#[test]
+fn test_solutions_round_trip() {
+    let board = Board::new(5, 6, Vec::new());
+    let pieces = notation::parse_pieces("KKQBBNRP").unwrap();
+    let mut boards = HashSet::new();
+    let mut packed = HashSet::new();
+    search::for_each_solution(&board, &pieces, |solution| {
+        let mut solution = solution.clone();
+        let compact = PackedBoard::try_from(&solution).unwrap();
+        solution.pieces.sort();
+        assert_eq!(solution, compact.unpack());
+        assert_eq!(solution, Board::from(&compact));
+        assert_eq!(pieces.len(), compact.len());
+        boards.insert(solution);
+        packed.insert(compact);
+    });
+    assert!(!boards.is_empty());
+    assert_eq!(boards.len(), packed.len());
+}
+
+#[test]
+fn test_packing_ignores_piece_order() {
+    let a = Board {
+        rows: 16,
+        cols: 16,
+        pieces: vec![
+            "N(16,16)".parse().unwrap(),
+            "K(1,1)".parse().unwrap(),
+            "N(2,5)".parse().unwrap(),
+        ],
+    };
+    let b: Board = "16x16 K(1,1) N(2,5) N(16,16)".parse().unwrap();
+    let packed = PackedBoard::try_from(&a).unwrap();
+    assert_eq!(PackedBoard::try_from(&b).unwrap(), packed);
+    assert_eq!((16, 16), packed.dimensions());
+    // Grouped by type: the knights, then the king.
+    assert_eq!(
+        vec!["N(2,5)", "N(16,16)", "K(1,1)"],
+        packed
+            .pieces()
+            .map(|piece| piece.to_string())
+            .collect::<Vec<_>>()
+    );
+    let empty = PackedBoard::try_from(&Board::new(1, 1, Vec::new())).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(Board::new(1, 1, Vec::new()), empty.unpack());
+}
+
+#[test]
+fn test_unpackable_boards() {
+    let large = Board::new(17, 16, Vec::new());
+    assert_eq!(
+        Err(PackError::TooLarge { rows: 17, cols: 16 }),
+        PackedBoard::try_from(&large)
+    );
+    let pieces = (1..=CAPACITY as i8 + 1)
+        .map(|col| format!("P(1,{})", col).parse().unwrap())
+        .collect();
+    let crowded = Board::new(2, CAPACITY as i8 + 1, pieces);
+    assert_eq!(
+        Err(PackError::TooManyPieces(CAPACITY + 1)),
+        PackedBoard::try_from(&crowded)
+    );
+    let coloured: Board = "3x3 wQ(1,1)".parse().unwrap();
+    assert_eq!(
+        Err(PackError::Coloured(coloured.pieces[0])),
+        PackedBoard::try_from(&coloured)
+    );
+    let off: Board = "3x3 Q(4,1)".parse().unwrap();
+    assert!(matches!(
+        PackedBoard::try_from(&off),
+        Err(PackError::OffBoard(_))
+    ));
+}
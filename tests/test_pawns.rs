@@ -0,0 +1,76 @@
+use chess::rules::Rules;
+use chess::*;
+
+mod common;
+
+fn pawn(row: i8, col: i8, colour: Option<Colour>) -> Piece {
+    Piece {
+        row,
+        col,
+        piece: ChessPiece::Pawn,
+        colour,
+    }
+}
+
+#[test]
+fn test_pawn_attacks_depend_on_colour() {
+    let attacks = |pawn: Piece, row: i8, col: i8| pawn.attacks(Piece { row, col, ..pawn });
+    for colour in [None, Some(Colour::White)] {
+        let pawn = pawn(3, 3, colour);
+        assert!(attacks(pawn, 2, 2) && attacks(pawn, 2, 4));
+        assert!(!attacks(pawn, 2, 3) && !attacks(pawn, 1, 1));
+        assert!(!attacks(pawn, 4, 2) && !attacks(pawn, 4, 4));
+    }
+    let black = pawn(3, 3, Some(Colour::Black));
+    assert!(attacks(black, 4, 2) && attacks(black, 4, 4));
+    assert!(!attacks(black, 2, 2) && !attacks(black, 2, 4));
+}
+
+#[test]
+fn test_pawn_counts() {
+    // A pawn on the bottom row attacks the top square of the other column.
+    assert_eq!(
+        4,
+        common::solution_count(2, 2, &[ChessPiece::Pawn, ChessPiece::Pawn])
+    );
+    let problems: [(i8, i8, &str); 4] =
+        [(3, 3, "PPP"), (4, 4, "KPPP"), (4, 5, "QPP"), (5, 4, "NRPP")];
+    for (rows, cols, pieces) in problems {
+        let pieces = notation::parse_pieces(pieces).unwrap();
+        let board = Board::new(rows, cols, Vec::new());
+        let expected = common::solution_count(rows, cols, &pieces) as u64;
+        assert_eq!(Some(expected), search::count(&board, &pieces).to_u64());
+        assert_eq!(Some(expected), dlx::count(&board, &pieces).to_u64());
+    }
+}
+
+#[test]
+fn test_pawns_off_back_ranks() {
+    let board = Board::new(4, 4, Vec::new());
+    let rules = Rules {
+        no_pawns_on_back_ranks: true,
+        ..Rules::default()
+    };
+    for row in 1..=4 {
+        let pawn = pawn(row, 2, None);
+        assert!(board.is_safe(pawn));
+        assert_eq!(row != 1 && row != 4, board.is_safe_with(pawn, &rules));
+        let king = Piece {
+            piece: ChessPiece::King,
+            ..pawn
+        };
+        assert!(board.is_safe_with(king, &rules));
+    }
+}
+
+#[test]
+fn test_pawn_notation() {
+    assert_eq!(
+        vec![ChessPiece::Pawn, ChessPiece::Pawn, ChessPiece::King],
+        notation::parse_pieces("2PK").unwrap()
+    );
+    let board: Board = "4x4 bP(2,3) P(3,1) wP(4,4)".parse().unwrap();
+    assert_eq!(pawn(2, 3, Some(Colour::Black)), board.pieces[0]);
+    assert_eq!(pawn(3, 1, None), board.pieces[1]);
+    assert_eq!("4x4 bP(2,3) P(3,1) wP(4,4)", board.to_string());
+}
@@ -0,0 +1,136 @@
+use chess::prune::ForwardChecker;
+use chess::*;
+
+mod common;
+
+#[test]
+fn test_solution_prefixes_fit() {
+    let problems: [(i8, i8, &str); 4] = [
+        (5, 5, "KKQB"),
+        (6, 6, "QQQQQQ"),
+        (4, 5, "KQBNR"),
+        (4, 4, "PPPK"),
+    ];
+    for (rows, cols, pieces) in problems {
+        let pieces = notation::parse_pieces(pieces).unwrap();
+        let checker = ForwardChecker::new(rows, cols);
+        let mut checked = 0;
+        search::for_each_solution(&Board::new(rows, cols, Vec::new()), &pieces, |solution| {
+            // Every placement order of a solution goes through boards the checker
+            // must keep, with pieces of one type in increasing square order.
+            let mut placed = solution.pieces.clone();
+            placed.sort_by_key(|piece| (piece.row, piece.col));
+            for split in 0..placed.len() {
+                let board = Board::new(rows, cols, placed[..split].to_vec());
+                let rest: Vec<ChessPiece> =
+                    placed[split..].iter().map(|piece| piece.piece).collect();
+                assert!(checker.fits(&board, &rest), "{} with {:?}", board, rest);
+            }
+            checked += 1;
+        });
+        assert!(checked > 0);
+    }
+}
+
+#[test]
+fn test_pruned_counts() {
+    let queens = [ChessPiece::Queen; 8];
+    let board = Board::new(8, 8, Vec::new());
+    assert_eq!(Some(92), search::count(&board, &queens).to_u64());
+    let pieces = notation::parse_pieces("KKQQBBN").unwrap();
+    assert_eq!(
+        search::count(&Board::new(5, 6, Vec::new()), &pieces),
+        search::count_with(
+            &Board::new(5, 6, Vec::new()),
+            &pieces,
+            &rules::Rules {
+                max_attackers: 0,
+                ..Default::default()
+            }
+        )
+    );
+}
+
+#[test]
+fn test_dead_boards() {
+    let checker = ForwardChecker::new(4, 4);
+    let rooks: Board = "4x4 R(1,1) R(2,2)".parse().unwrap();
+    // Four squares are out of reach, although only two more rooks could go there.
+    assert!(checker.fits(&rooks, &[ChessPiece::Rook; 4]));
+    assert!(!checker.fits(&rooks, &[ChessPiece::Rook; 5]));
+    // (1,2) is free, but another king would come before the one on the board.
+    let king: Board = "4x4 K(4,4)".parse().unwrap();
+    assert!(!checker.fits(&king, &[ChessPiece::King]));
+    assert!(checker.fits(&king, &[ChessPiece::Knight]));
+    // A queen on every free square would still leave one queen without a square.
+    let queen: Board = "4x4 Q(2,2)".parse().unwrap();
+    assert!(!checker.fits(&queen, &[ChessPiece::Queen; 4]));
+}
+
+#[test]
+fn test_unchecked_boards() {
+    let large: Board = "12x12 Q(1,1)".parse().unwrap();
+    assert!(ForwardChecker::new(12, 12).fits(&large, &[ChessPiece::Queen; 200]));
+    let small: Board = "3x3 Q(2,2)".parse().unwrap();
+    // The checker is for another size.
+    assert!(ForwardChecker::new(4, 4).fits(&small, &[ChessPiece::Queen]));
+    assert!(!ForwardChecker::new(3, 3).fits(&small, &[ChessPiece::Queen]));
+}
+
+#[test]
+fn test_prepopulated_boards() {
+    // The pieces already on the board don't come in the order of the search.
+    let problems = [
+        ("4x4 K(4,4)", "KK", 39),
+        ("5x5 Q(5,5)", "QQQQ", 2),
+        ("4x4 R(4,4)", "RRR", 6),
+        ("5x5 N(3,3) K(5,5)", "KKN", 125),
+    ];
+    for (board, pieces, expected) in problems {
+        let board: Board = board.parse().unwrap();
+        let pieces = notation::parse_pieces(pieces).unwrap();
+        assert_eq!(oracle::count(&board, &pieces), expected);
+        let solutions = common::solutions(&board, &pieces);
+        assert_eq!(solutions.len(), expected, "{} with {:?}", board, pieces);
+    }
+}
+
+/// Returns the boards [`solution_with_stats`] expands and prunes placing `pieces`.
+fn expanded_and_pruned(board: &Board, pieces: &[ChessPiece]) -> (u64, u64) {
+    let mut solutions = std::collections::HashSet::new();
+    let (_, stats) = solution_with_stats(
+        &mut common::board_stack(board, pieces),
+        &mut solutions,
+        &mut std::collections::HashSet::new(),
+    );
+    (stats.expanded, stats.pruned)
+}
+
+/// Returns the boards [`solution_with_stats`] would expand without forward checking:
+/// every safe placement of each proper prefix of `pieces`, once.
+fn unpruned_nodes(board: &Board, pieces: &[ChessPiece]) -> u64 {
+    (0..pieces.len())
+        .map(|depth| search::count(board, &pieces[..depth]).to_u64().unwrap())
+        .sum()
+}
+
+#[test]
+fn test_pruning_expands_fewer_boards() {
+    // Boards of more than 128 squares are not checked, so every prefix is expanded.
+    let queens = [ChessPiece::Queen; 3];
+    let large = Board::new(12, 12, Vec::new());
+    assert_eq!(
+        (unpruned_nodes(&large, &queens), 0),
+        expanded_and_pruned(&large, &queens)
+    );
+    let queens = [ChessPiece::Queen; 8];
+    let board = Board::new(8, 8, Vec::new());
+    let (expanded, pruned) = expanded_and_pruned(&board, &queens);
+    assert!(pruned > 0);
+    assert!(
+        2 * expanded < unpruned_nodes(&board, &queens),
+        "{} of {} boards expanded",
+        expanded,
+        unpruned_nodes(&board, &queens)
+    );
+}
@@ -0,0 +1,44 @@
+use chess::sample::Sampler;
+use chess::*;
+use std::collections::HashMap;
+
+mod common;
+
+#[test]
+fn test_samples_are_uniform_solutions() {
+    let pieces = [ChessPiece::Queen, ChessPiece::Queen, ChessPiece::Bishop];
+    let board = Board::new(4, 4, Vec::new());
+    let expected = common::solutions(&board, &pieces);
+    let mut sampler = Sampler::new(board, &pieces, 2024);
+    assert_eq!(Some(expected.len() as u64), sampler.total().to_u64());
+
+    let draws = 72 * 200;
+    let mut frequencies: HashMap<Board, usize> = HashMap::new();
+    for _ in 0..draws {
+        let board = sampler.sample().unwrap();
+        assert!(expected.contains(&board));
+        *frequencies.entry(board).or_insert(0) += 1;
+    }
+    // Every one of the 72 solutions is expected 200 times; allow for a wide margin.
+    assert_eq!(expected.len(), frequencies.len());
+    assert!(frequencies.values().all(|&frequency| (130..=270).contains(&frequency)));
+}
+
+#[test]
+fn test_same_seed_draws_same_solutions() {
+    let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Knight];
+    let board = Board::new(5, 5, Vec::new());
+    let mut a = Sampler::new(board.clone(), &pieces, 1);
+    let mut b = Sampler::new(board, &pieces, 1);
+    for _ in 0..20 {
+        assert_eq!(a.sample(), b.sample());
+    }
+}
+
+#[test]
+fn test_sample_without_solutions() {
+    let pieces = [ChessPiece::Queen, ChessPiece::Queen];
+    let mut sampler = Sampler::new(Board::new(2, 2, Vec::new()), &pieces, 3);
+    assert!(sampler.total().is_zero());
+    assert_eq!(None, sampler.sample());
+}
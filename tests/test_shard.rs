@@ -0,0 +1,78 @@
+use chess::*;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+mod common;
+
+fn shard_total(board: &Board, pieces: &[ChessPiece], of: usize) -> u64 {
+    (0..of)
+        .map(|index| {
+            let mut checkpoint = shard::shard(board, pieces, index, of);
+            checkpoint.run(u64::MAX);
+            checkpoint.solutions
+        })
+        .sum::<bigint::BigUint>()
+        .to_u64()
+        .unwrap()
+}
+
+#[test]
+fn test_shard_totals_match_solution() {
+    let pieces = [
+        ChessPiece::King,
+        ChessPiece::King,
+        ChessPiece::Queen,
+        ChessPiece::Queen,
+    ];
+    let board = Board::new(5, 5, Vec::new());
+    let expected = common::solution_count(5, 5, &pieces) as u64;
+    for of in [1, 2, 3, 7, 25, 26, 100, 1000] {
+        assert_eq!(expected, shard_total(&board, &pieces, of), "{} shards", of);
+    }
+}
+
+#[test]
+fn test_more_shards_than_configurations() {
+    let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+    let board = Board::new(3, 3, Vec::new());
+    assert_eq!(4, shard_total(&board, &pieces, 50));
+}
+
+#[test]
+fn test_shard_solutions_partition_solution() {
+    let pieces = [ChessPiece::Queen, ChessPiece::Bishop, ChessPiece::Rook];
+    let board = Board::new(5, 5, Vec::new());
+    let mut results = Vec::new();
+    for index in 0..4 {
+        let mut boards = Vec::new();
+        shard::shard(&board, &pieces, index, 4).for_each_solution(|solution| {
+            let mut solution = solution.clone();
+            solution.pieces.sort();
+            boards.push(solution);
+        });
+        let mut result = Vec::new();
+        shard::write_result(
+            &mut result,
+            &bigint::BigUint::from(boards.len() as u64),
+            &boards,
+        )
+        .unwrap();
+        results.push(result);
+    }
+    let (count, boards) = shard::merge(results.iter().map(|result| result.as_slice())).unwrap();
+    let merged: HashSet<Rc<Board>> = boards.into_iter().map(Rc::new).collect();
+    assert_eq!("1224", count.to_string());
+    assert_eq!(1224, merged.len());
+    assert_eq!(
+        common::solutions(&Board::new(5, 5, Vec::new()), &pieces),
+        merged
+    );
+}
+
+#[test]
+fn test_merge_counts_only() {
+    let results = ["# count 12\n", "# count 30\n", "3x3 K(1,1) K(1,3)\n"];
+    let (count, boards) = shard::merge(results.iter().map(|result| result.as_bytes())).unwrap();
+    assert_eq!("43", count.to_string());
+    assert_eq!(1, boards.len());
+}
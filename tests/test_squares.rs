@@ -0,0 +1,63 @@
+use chess::*;
+
+#[test]
+fn test_queen_attacks() {
+    let board: Board = "8x8 Q(4,4)".parse().unwrap();
+    assert_eq!(27, board.attacked_squares().count());
+    assert_eq!(64 - 27 - 1, board.free_squares().count());
+    assert!(board.attackers_of(4, 4).next().is_none());
+    assert_eq!(
+        vec![board.pieces[0]],
+        board.attackers_of(8, 8).collect::<Vec<_>>()
+    );
+    assert!(board.attackers_of(5, 6).next().is_none());
+}
+
+#[test]
+fn test_squares_partition_the_board() {
+    let board: Board = "5x6 K(1,1) N(2,4) B(4,2) P(5,6) R(5,1)".parse().unwrap();
+    let attacked: Vec<(i8, i8)> = board.attacked_squares().collect();
+    let free: Vec<(i8, i8)> = board.free_squares().collect();
+    for (row, col) in board.squares() {
+        let occupied = board
+            .pieces
+            .iter()
+            .any(|piece| (piece.row, piece.col) == (row, col));
+        let has_attackers = board.attackers_of(row, col).count() > 0;
+        assert_eq!(has_attackers, attacked.contains(&(row, col)));
+        assert_eq!(!occupied && !has_attackers, free.contains(&(row, col)));
+    }
+    // (2,1) is next to the king and on the rook's column.
+    assert!(attacked.contains(&(2, 1)));
+    assert_eq!(
+        vec!["K(1,1)", "R(5,1)"],
+        board
+            .attackers_of(2, 1)
+            .map(|piece| piece.to_string())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_solutions_have_no_attacked_pieces() {
+    let pieces = notation::parse_pieces("KKQB").unwrap();
+    let board = Board::new(5, 5, Vec::new());
+    let mut checked = 0;
+    search::for_each_solution(&board, &pieces, |solution| {
+        for piece in &solution.pieces {
+            assert_eq!(0, solution.attackers_of(piece.row, piece.col).count());
+        }
+        // Every free square is one where another piece could go unattacked.
+        for (row, col) in solution.free_squares() {
+            let king = Piece {
+                row,
+                col,
+                piece: ChessPiece::King,
+                colour: None,
+            };
+            assert!(solution.pieces.iter().all(|&piece| !piece.attacks(king)));
+        }
+        checked += 1;
+    });
+    assert!(checked > 0);
+}
@@ -0,0 +1,74 @@
+use chess::notation::parse_pieces;
+use chess::*;
+use std::collections::HashSet;
+
+mod common;
+
+fn solve(rows: i8, cols: i8, pieces: &str) -> (usize, stats::SearchStats) {
+    let pieces = parse_pieces(pieces).unwrap();
+    let mut solutions = HashSet::new();
+    let mut board_stack = common::board_stack(&Board::new(rows, cols, Vec::new()), &pieces);
+    let (solutions, stats) =
+        solution_with_stats(&mut board_stack, &mut solutions, &mut HashSet::new());
+    (solutions.len(), stats)
+}
+
+#[test]
+fn test_stats_add_up() {
+    for (rows, cols, pieces) in [(5, 5, "KKQB"), (4, 5, "NNRQ"), (6, 6, "QQQQQQ")] {
+        let (count, stats) = solve(rows, cols, pieces);
+        let expected = search::count(
+            &Board::new(rows, cols, Vec::new()),
+            &parse_pieces(pieces).unwrap(),
+        );
+        assert_eq!(expected.to_u64(), Some(count as u64));
+        let squares = rows as u64 * cols as u64;
+        assert_eq!(squares * stats.expanded, stats.safety_checks);
+        assert_eq!(
+            stats.expanded,
+            stats.depths.iter().map(|depth| depth.expanded).sum::<u64>()
+        );
+        // Boards are expanded with anything from no pieces to all but one on them.
+        assert_eq!(pieces.len(), stats.depths.len());
+        assert!(stats.max_frontier as u64 <= stats.expanded);
+        // The solutions alone take a board each.
+        assert!(stats.peak_memory > count * std::mem::size_of::<Board>());
+    }
+}
+
+#[test]
+fn test_duplicates() {
+    // The two kings reach each board with both in two orders.
+    let (count, stats) = solve(4, 4, "KKQ");
+    assert!(count > 0);
+    assert!(stats.duplicates > 0);
+    // A single kind of piece placed once has no repeats.
+    let (count, stats) = solve(4, 4, "Q");
+    assert_eq!(16, count);
+    assert_eq!(0, stats.duplicates);
+    assert_eq!(1, stats.expanded);
+    assert_eq!(1, stats.max_frontier);
+}
+
+#[test]
+fn test_stats_report() {
+    let (_, stats) = solve(3, 3, "KKR");
+    let report = stats.to_string();
+    assert!(report.contains(&format!("Boards expanded      {}", stats.expanded)));
+    assert!(report.contains("Duplicates rejected"));
+    assert_eq!(stats.depths.len() + 7, report.lines().count());
+}
+
+#[test]
+fn test_no_pieces() {
+    // With nothing to place, the starting board is the only solution.
+    let (count, stats) = solve(3, 3, "");
+    assert_eq!(1, count);
+    assert_eq!(0, stats.expanded);
+    assert_eq!(1, common::solution_count(3, 3, &[]));
+    let board: Board = "3x3 K(2,2)".parse().unwrap();
+    assert_eq!(
+        HashSet::from([std::rc::Rc::new(board.clone())]),
+        common::solutions(&board, &[])
+    );
+}
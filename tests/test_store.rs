@@ -0,0 +1,124 @@
+use chess::binary::{self, Header};
+use chess::packed::PackedBoard;
+use chess::store::{Filter, Quantifier, Query, Region, Store};
+use chess::*;
+use std::io::ErrorKind;
+
+fn solutions(rows: i8, cols: i8, pieces: &str) -> (Vec<ChessPiece>, Vec<Board>) {
+    let pieces = notation::parse_pieces(pieces).unwrap();
+    let mut boards = Vec::new();
+    search::for_each_solution(&Board::new(rows, cols, Vec::new()), &pieces, |solution| {
+        let mut solution = solution.clone();
+        solution.pieces.sort();
+        boards.push(solution);
+    });
+    (pieces, boards)
+}
+
+#[test]
+fn test_queries_agree_with_matches() {
+    let (_, boards) = solutions(5, 5, "KKQBN");
+    let mut store = Store::new();
+    for board in &boards {
+        store.insert(board).unwrap();
+    }
+    assert_eq!(store.len(), boards.len());
+    for text in [
+        "",
+        "Q(3,3)",
+        "all:K(edge)",
+        "no:_(1,_) N(_,2..4)",
+        "_(2..3,1..2) no:B(edge)",
+        "all:_(1..4,1..4)",
+    ] {
+        let query: Query = text.parse().unwrap();
+        let expected: Vec<Board> = boards
+            .iter()
+            .filter(|board| query.matches(board))
+            .cloned()
+            .collect();
+        assert_eq!(store.count(&query), expected.len(), "{}", text);
+        assert_eq!(
+            store.select(&query).collect::<Vec<_>>(),
+            expected,
+            "{}",
+            text
+        );
+    }
+    assert_eq!(store.count(&Query::default()), boards.len());
+}
+
+#[test]
+fn test_filters() {
+    let board: Board = "6x6 K(1,1) K(3,6) Q(4,4) N(2,2)".parse().unwrap();
+    let passes = |filter: Filter| Query::new(vec![filter]).matches(&board);
+    let king = Some(ChessPiece::King);
+    assert!(passes(Filter::any(king, Region::row(3))));
+    assert!(!passes(Filter::all(king, Region::row(3))));
+    assert!(passes(Filter::all(king, Region::Edge)));
+    assert!(passes(Filter::none(king, Region::col(4))));
+    assert!(passes(Filter::any(None, Region::square(2, 2))));
+    assert!(!passes(Filter::any(None, Region::area(5..=6, 1..=6))));
+    // No rook stands anywhere, so all of them stand in the corner.
+    let rook = Some(ChessPiece::Rook);
+    assert!(passes(Filter::all(rook, Region::square(1, 1))));
+    assert!(!passes(Filter::any(rook, Region::Edge)));
+}
+
+#[test]
+fn test_query_notation() {
+    let query: Query = "Q(4,4) all:K(edge) no:N(1,_) _(2..3,1..2)".parse().unwrap();
+    assert_eq!(query.filters.len(), 4);
+    assert_eq!(
+        query.filters[1],
+        Filter::all(Some(ChessPiece::King), Region::Edge)
+    );
+    assert_eq!(query.filters[2].quantifier, Quantifier::No);
+    assert_eq!(query.filters[2].region, Region::row(1));
+    assert_eq!(query.filters[3].region, Region::area(2..=3, 1..=2));
+    assert_eq!(
+        query.to_string(),
+        "Q(4,4) all:K(edge) no:N(1,_) _(2..3,1..2)"
+    );
+    for invalid in [
+        "X(1,1)",
+        "Q",
+        "Q(1)",
+        "Q(a,1)",
+        "some:Q(1,1)",
+        "Q(1,1",
+        "Q(5..3,_)",
+        "Q(1,4..2)",
+    ] {
+        assert!(invalid.parse::<Query>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_load_text_and_binary() {
+    let (pieces, boards) = solutions(4, 5, "KKRN");
+    let mut text = Vec::new();
+    notation::write_solutions(&mut text, &boards).unwrap();
+    let packed: Vec<PackedBoard> = boards
+        .iter()
+        .map(|board| PackedBoard::try_from(board).unwrap())
+        .collect();
+    let mut file = Vec::new();
+    let header = Header::new(4, 5, &pieces, packed.len() as u64);
+    binary::write(&mut file, &header, &packed).unwrap();
+
+    let mut store = Store::new();
+    assert_eq!(store.load(&text[..]).unwrap(), boards.len());
+    assert_eq!(store.load(&file[..]).unwrap(), boards.len());
+    assert_eq!(store.len(), 2 * boards.len());
+    let query: Query = "R(1,_)".parse().unwrap();
+    let expected = boards.iter().filter(|board| query.matches(board)).count();
+    assert!(expected > 0);
+    assert_eq!(store.count(&query), 2 * expected);
+
+    // A broken file adds nothing.
+    let error = store.load(&file[..file.len() - 1]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert!(store.load(&b"4x5 K(1,1) X(2,2)\n"[..]).is_err());
+    assert_eq!(store.len(), 2 * boards.len());
+}
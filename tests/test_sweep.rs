@@ -0,0 +1,62 @@
+use chess::backend::Backend;
+use chess::notation::parse_pieces;
+use chess::sweep::Sweep;
+use chess::*;
+
+#[test]
+fn test_sweep_matches_counts() {
+    let pattern = parse_pieces("KN").unwrap();
+    let table = Sweep::new(1..=4, 2..=5, pattern.clone(), 0..=3).run();
+    assert_eq!(vec![0, 1, 2, 3], table.counts);
+    assert_eq!(16, table.rows.len());
+    for (rows, cols, counts) in &table.rows {
+        for (&copies, count) in table.counts.iter().zip(counts) {
+            let pieces: Vec<ChessPiece> = (0..copies).flat_map(|_| pattern.clone()).collect();
+            assert_eq!(
+                &search::count(&Board::new(*rows, *cols, Vec::new()), &pieces),
+                count,
+                "{}x{} {}",
+                rows,
+                cols,
+                copies
+            );
+        }
+    }
+}
+
+#[test]
+fn test_transposed_boards_are_reused() {
+    let kings = vec![ChessPiece::King];
+    let table = Sweep::new(1..=5, 1..=5, kings.clone(), 0..=4).run();
+    // 15 distinct board shapes, with no count past the first zero.
+    assert!(table.solved < 15 * 5);
+    let square = Sweep::new(1..=5, 1..=5, kings.clone(), 0..=4)
+        .square()
+        .run();
+    assert_eq!(5, square.rows.len());
+    // Pawns attack towards row 1, so transposed boards are counted separately.
+    let mut pawns = Sweep::new(2..=5, 2..=5, parse_pieces("PPR").unwrap(), 0..=1);
+    pawns.backend = Backend::DancingLinks;
+    let table = pawns.run();
+    let count = |rows, cols| {
+        table
+            .rows
+            .iter()
+            .find(|&&(r, c, _)| (r, c) == (rows, cols))
+            .map(|(_, _, counts)| counts[1].to_u64().unwrap())
+            .unwrap()
+    };
+    assert_eq!(39, count(2, 5));
+    assert_eq!(36, count(5, 2));
+    assert_eq!(16 * 2, table.solved);
+}
+
+#[test]
+fn test_table_formats() {
+    let table = Sweep::new(1..=2, 2..=2, vec![ChessPiece::Rook], 1..=2).run();
+    assert_eq!("rows,cols,1,2\n1,2,2,0\n2,2,4,2\n", table.to_csv());
+    assert_eq!(
+        "| board | 1×R | 2×R |\n|---|---:|---:|\n| 1x2 | 2 | 0 |\n| 2x2 | 4 | 2 |\n",
+        table.to_markdown()
+    );
+}
@@ -0,0 +1,71 @@
+use chess::bigint::BigUint;
+use chess::transfer::{Unsupported, MAX_COLS};
+use chess::*;
+
+mod common;
+
+fn solution_count(rows: i8, cols: i8, pieces: &[ChessPiece]) -> u64 {
+    if pieces.is_empty() {
+        return 1;
+    }
+    common::solution_count(rows, cols, pieces) as u64
+}
+
+#[test]
+fn test_transfer_matches_solution_on_small_boards() {
+    for cols in 1..=4 {
+        for rows in 1..=5 {
+            for kings in 0..=3 {
+                for knights in 0..=2 {
+                    let mut pieces = vec![ChessPiece::King; kings];
+                    pieces.extend(vec![ChessPiece::Knight; knights]);
+                    let count = transfer::count(rows as usize, cols as usize, &pieces).unwrap();
+                    assert_eq!(
+                        Some(solution_count(rows, cols, &pieces)),
+                        count.to_u64(),
+                        "{}x{} {}",
+                        rows,
+                        cols,
+                        notation::format_pieces(&pieces)
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_transfer_long_boards() {
+    let kings = [ChessPiece::King, ChessPiece::King];
+    let knights = [ChessPiece::Knight, ChessPiece::Knight];
+    // All pairs of squares minus the pairs a king (or knight) move apart.
+    assert_eq!(
+        Some(7_998_000 - 12_990),
+        transfer::count(1000, 4, &kings).unwrap().to_u64()
+    );
+    assert_eq!(
+        Some(7_998_000 - 9_984),
+        transfer::count(1000, 4, &knights).unwrap().to_u64()
+    );
+}
+
+#[test]
+fn test_transfer_counts_beyond_u64() {
+    let kings = vec![ChessPiece::King; 40];
+    let count = transfer::count(100, 4, &kings).unwrap();
+    assert_eq!(None, count.to_u64());
+    assert!(count.to_string().len() > 20);
+}
+
+#[test]
+fn test_transfer_rejects_unsupported_problems() {
+    assert_eq!(
+        Err(Unsupported::Piece(ChessPiece::Queen)),
+        transfer::count(4, 4, &[ChessPiece::King, ChessPiece::Queen])
+    );
+    assert_eq!(
+        Err(Unsupported::Columns(MAX_COLS + 1)),
+        transfer::count(4, MAX_COLS + 1, &[ChessPiece::King])
+    );
+    assert_eq!(Ok(BigUint::from(1)), transfer::count(4, 4, &[]));
+}
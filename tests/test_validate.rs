@@ -0,0 +1,74 @@
+use chess::validate::Violation;
+use chess::*;
+use std::collections::{HashSet, VecDeque};
+use std::rc::Rc;
+
+fn board(line: &str) -> Board {
+    line.parse().unwrap()
+}
+
+#[test]
+fn test_solutions_are_valid() {
+    let pieces = [ChessPiece::Queen, ChessPiece::Bishop, ChessPiece::Rook];
+    let mut solutions: HashSet<Rc<Board>> = HashSet::new();
+    let mut board_stack: VecDeque<(Rc<Board>, &[ChessPiece])> = VecDeque::new();
+    board_stack.push_front((Rc::new(Board::new(5, 5, Vec::new())), &pieces));
+    solution(&mut board_stack, &mut solutions, &mut HashSet::new());
+    assert_eq!(1224, solutions.len());
+    assert!(solutions
+        .iter()
+        .all(|board| board.validate(&pieces).is_ok()));
+}
+
+#[test]
+fn test_violations() {
+    let pieces = [ChessPiece::King, ChessPiece::King, ChessPiece::Rook];
+    assert_eq!(Ok(()), board("3x3 K(1,1) K(1,3) R(3,2)").validate(&pieces));
+    assert_eq!(
+        Err(Violation::Dimensions { rows: 0, cols: 3 }),
+        Board::new(0, 3, Vec::new()).validate(&[])
+    );
+    assert!(matches!(
+        board("3x3 K(1,1) K(1,4) R(3,2)").validate(&pieces),
+        Err(Violation::OffBoard(Piece { row: 1, col: 4, .. }))
+    ));
+    assert!(matches!(
+        board("3x3 K(1,1) K(1,3) R(1,1)").validate(&pieces),
+        Err(Violation::SameSquare(..))
+    ));
+    assert_eq!(
+        Err(Violation::Count {
+            piece: ChessPiece::King,
+            expected: 2,
+            found: 1
+        }),
+        board("3x3 K(1,1) R(3,2)").validate(&pieces)
+    );
+    assert_eq!(
+        Err(Violation::Count {
+            piece: ChessPiece::Queen,
+            expected: 0,
+            found: 1
+        }),
+        board("3x3 K(1,1) K(1,3) R(3,2) Q(2,2)").validate(&pieces)
+    );
+    assert!(matches!(
+        board("3x3 K(1,1) K(1,3) R(2,2)").validate(&pieces),
+        Err(Violation::Attack { .. })
+    ));
+}
+
+#[test]
+fn test_duplicate_piece_is_same_square() {
+    let king = Piece {
+        row: 1,
+        col: 1,
+        piece: ChessPiece::King,
+        colour: None,
+    };
+    let board = Board::new(3, 3, vec![king, king]);
+    assert_eq!(
+        Err(Violation::SameSquare(king, king)),
+        board.validate(&[ChessPiece::King, ChessPiece::King])
+    );
+}